@@ -0,0 +1,67 @@
+//! Measures the cost per hop of `Process::pointer_chain` (one
+//! `process_vm_readv` per hop), against following the same chain through a
+//! `ProcessReader` per hop (as `pointer_chain` used to), and with
+//! `Process::read_word` (one `PTRACE_PEEKDATA` per hop).
+//!
+//! The target is stopped, since `PTRACE_PEEKDATA` only works on a stopped
+//! tracee; `pointer_chain` doesn't need it to be.
+//!
+//! Run with `cargo bench --bench pointer_chain`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{hint::black_box, io::Read, time::Instant};
+
+use common::{writable_address, Target};
+
+const HOPS: usize = 64;
+const ITERATIONS: u32 = 1000;
+
+fn main() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    // Each slot points at the next one, so every hop lands one word further
+    let base = writable_address(&mut proc);
+    let chain: Vec<usize> = (1..=HOPS).map(|hop| base + hop * 8).collect();
+    proc.write_array(base, &chain).unwrap();
+    let offsets = [0; HOPS];
+    assert_eq!(proc.pointer_chain(base, &offsets).unwrap(), base + HOPS * 8);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(proc.pointer_chain(base, &offsets).unwrap());
+    }
+    let pointer_chain = start.elapsed() / ITERATIONS / HOPS as u32;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut address = base;
+        for _ in 0..HOPS {
+            let mut bytes = [0; 8];
+            proc.reader(address, 8)
+                .unwrap()
+                .read_exact(&mut bytes)
+                .unwrap();
+            address = usize::from_ne_bytes(bytes);
+        }
+        black_box(address);
+    }
+    let reader = start.elapsed() / ITERATIONS / HOPS as u32;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut address = base;
+        for _ in 0..HOPS {
+            address = proc.read_word(address).unwrap() as usize;
+        }
+        black_box(address);
+    }
+    let read_word = start.elapsed() / ITERATIONS / HOPS as u32;
+
+    println!("per hop, over a {HOPS}-hop chain:");
+    println!("  pointer_chain: {pointer_chain:>10?}  (process_vm_readv)");
+    println!("  ProcessReader: {reader:>10?}  (process_vm_readv)");
+    println!("  read_word:     {read_word:>10?}  (PTRACE_PEEKDATA)");
+}
@@ -0,0 +1,48 @@
+//! Compares reading 64 KiB through `ProcessReader` (one `process_vm_readv`)
+//! against reading it a word at a time (one `PTRACE_PEEKDATA` per word).
+//!
+//! Run with `cargo bench --bench read`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{hint::black_box, io::Read, time::Instant};
+
+use common::Target;
+
+const LEN: usize = 64 * 1024;
+const ITERATIONS: u32 = 100;
+
+fn main() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = proc
+        .regions()
+        .unwrap()
+        .into_iter()
+        .find(|region| region.perms.read && region.end - region.start >= LEN)
+        .expect("no readable 64 KiB region")
+        .start;
+
+    let mut buf = vec![0; LEN];
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut reader = proc.reader(address, LEN).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        black_box(&buf);
+    }
+    let batched = start.elapsed() / ITERATIONS;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for offset in (0..LEN).step_by(8) {
+            black_box(proc.read_word(address + offset).unwrap());
+        }
+    }
+    let by_word = start.elapsed() / ITERATIONS;
+
+    let words = LEN / 8;
+    println!("reading {LEN} bytes:");
+    println!("  ProcessReader: {batched:>12?}  (1 process_vm_readv)");
+    println!("  word by word:  {by_word:>12?}  ({words} PTRACE_PEEKDATA)");
+}
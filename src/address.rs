@@ -0,0 +1,102 @@
+use std::mem::size_of;
+
+/// An integer type that can hold an address in a process' memory.
+///
+/// Decoding only looks at the first `size_of::<Self>()` bytes; shorter slices
+/// are zero-extended (so a 4-byte pointer can be decoded into a `usize`).
+pub trait Address: Sized + Copy {
+    /// Decodes an address from little-endian bytes. The same as `Address::from_bytes_le`.
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self::from_bytes_le(bytes)
+    }
+
+    /// Decodes an address from little-endian bytes.
+    fn from_bytes_le(bytes: &[u8]) -> Self;
+
+    /// Decodes an address from big-endian bytes.
+    fn from_bytes_be(bytes: &[u8]) -> Self;
+
+    /// Encodes an address as little-endian bytes. The same as `Address::to_bytes_le`.
+    fn to_bytes(self) -> Vec<u8> {
+        self.to_bytes_le()
+    }
+
+    /// Encodes an address as little-endian bytes.
+    fn to_bytes_le(self) -> Vec<u8>;
+
+    /// Encodes an address as big-endian bytes.
+    fn to_bytes_be(self) -> Vec<u8>;
+}
+
+macro_rules! impl_address {
+    ($($ty:ty),*) => {
+        $(impl Address for $ty {
+            fn from_bytes_le(bytes: &[u8]) -> Self {
+                bytes
+                    .iter()
+                    .take(size_of::<Self>())
+                    .enumerate()
+                    .fold(0, |address, (i, byte)| address | (*byte as Self) << (i * 8))
+            }
+
+            fn from_bytes_be(bytes: &[u8]) -> Self {
+                bytes
+                    .iter()
+                    .take(size_of::<Self>())
+                    .fold(0, |address, byte| address << 8 | *byte as Self)
+            }
+
+            fn to_bytes_le(self) -> Vec<u8> {
+                self.to_le_bytes().to_vec()
+            }
+
+            fn to_bytes_be(self) -> Vec<u8> {
+                self.to_be_bytes().to_vec()
+            }
+        })*
+    };
+}
+
+impl_address!(usize, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; 8] = [0x12, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn decodes_both_endians() {
+        assert_eq!(u32::from_bytes_le(&BYTES), 0x3412);
+        assert_eq!(u32::from_bytes_be(&BYTES), 0x1234_0000);
+        assert_eq!(u64::from_bytes_le(&BYTES), 0x3412);
+        assert_eq!(u64::from_bytes_be(&BYTES), 0x1234_0000_0000_0000);
+        assert_eq!(u64::from_bytes(&BYTES), u64::from_bytes_le(&BYTES));
+    }
+
+    #[test]
+    fn zero_extends_short_input() {
+        // A 4-byte pointer read into a `usize`
+        assert_eq!(usize::from_bytes_le(&[0x78, 0x56, 0x34, 0x12]), 0x1234_5678);
+        assert_eq!(usize::from_bytes_be(&[0x12, 0x34, 0x56, 0x78]), 0x1234_5678);
+    }
+
+    #[test]
+    fn round_trips_both_endians() {
+        for value in [0, 1, 0x1234_5678, u32::MAX] {
+            assert_eq!(u32::from_bytes_le(&value.to_bytes_le()), value);
+            assert_eq!(u32::from_bytes_be(&value.to_bytes_be()), value);
+        }
+
+        for value in [0, 1, 0x1234_5678_9abc_def0, u64::MAX] {
+            assert_eq!(u64::from_bytes_le(&value.to_bytes_le()), value);
+            assert_eq!(u64::from_bytes_be(&value.to_bytes_be()), value);
+        }
+
+        for value in [0, 1, 0x1234_5678, usize::MAX] {
+            assert_eq!(usize::from_bytes_le(&value.to_bytes_le()), value);
+            assert_eq!(usize::from_bytes_be(&value.to_bytes_be()), value);
+            assert_eq!(usize::from_bytes(&value.to_bytes()), value);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::io;
+
+use nix::sys::{ptrace, signal::Signal};
+
+#[cfg(target_arch = "x86_64")]
+use crate::regs::user_fpregs_struct;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use crate::regs::user_regs_struct;
+use crate::{Pid, WaitStatus};
+
+/// The platform-specific operations `Process` is built on.
+///
+/// Each supported platform implements this once, and the right one is picked
+/// with `#[cfg]` as `Platform`. Everything `Process` does to an attached
+/// process (or one of its threads) goes through here; only reading `/proc`
+/// happens elsewhere.
+///
+/// This isn't an OS-neutral interface: it's shaped after ptrace, and speaks
+/// nix's `Signal`, `ptrace::Options` and `WaitStatus` and libc's register
+/// structs. A new platform has to map its debugging API onto those.
+pub(crate) trait Backend {
+    /// A handle to an attached process or thread.
+    type Handle: Copy + Send;
+
+    /// Attaches to a process, letting it keep running.
+    fn attach(pid: Pid) -> io::Result<Self::Handle>;
+
+    /// Attaches to a process, and waits for it to stop.
+    fn attach_stopped(pid: Pid) -> io::Result<Self::Handle>;
+
+    /// Attaches to a process without stopping it, setting `options`
+    /// (`PTRACE_SEIZE`).
+    fn seize(pid: Pid, options: ptrace::Options) -> io::Result<Self::Handle>;
+
+    /// Asks a seized process to stop (`PTRACE_INTERRUPT`). Doesn't wait for
+    /// it to.
+    fn interrupt(handle: Self::Handle) -> io::Result<()>;
+
+    /// Stops a process, and waits for it to stop.
+    fn stop(handle: Self::Handle) -> io::Result<()>;
+
+    /// Continues a stopped process, delivering `sig` to it as it resumes.
+    fn cont(handle: Self::Handle, sig: Option<Signal>) -> io::Result<()>;
+
+    /// Continues a stopped process until its next syscall entry or exit,
+    /// delivering `sig` to it as it resumes.
+    fn syscall(handle: Self::Handle, sig: Option<Signal>) -> io::Result<()>;
+
+    /// Executes a single instruction, delivering `sig` to the process as it
+    /// resumes. Doesn't wait for it to stop again.
+    fn step(handle: Self::Handle, sig: Option<Signal>) -> io::Result<()>;
+
+    /// Waits for a process (or thread) to change state.
+    fn wait(handle: Self::Handle) -> io::Result<WaitStatus>;
+
+    /// Sets the ptrace options for a stopped process.
+    fn set_options(handle: Self::Handle, options: ptrace::Options) -> io::Result<()>;
+
+    /// Detaches from a stopped process, delivering `sig` to it as it resumes.
+    fn detach(handle: Self::Handle, sig: Option<Signal>) -> io::Result<()>;
+
+    /// Kills a process, and waits for it to die.
+    fn kill(handle: Self::Handle) -> io::Result<()>;
+
+    /// Reads a single word from a process' memory.
+    fn read_word(handle: Self::Handle, address: usize) -> io::Result<isize>;
+
+    /// Reads into `buf` from a process' memory, returning how many bytes were
+    /// read. Works whether or not the process is stopped, but stops at the
+    /// first byte it can't read.
+    fn read_memory(handle: Self::Handle, address: usize, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Reads `len` bytes from each of `addresses` into consecutive chunks of
+    /// `buf`, returning how many bytes were read in total. Like
+    /// `Backend::read_memory`, this stops at the first byte it can't read.
+    fn read_memory_many(
+        handle: Self::Handle,
+        addresses: &[usize],
+        len: usize,
+        buf: &mut [u8],
+    ) -> io::Result<usize>;
+
+    /// Writes a single word into a process' memory. The process should
+    /// already be stopped.
+    fn write_word(handle: Self::Handle, address: usize, data: isize) -> io::Result<()>;
+
+    /// Writes `buf` into a process' memory, returning how many bytes were
+    /// written. Works whether or not the process is stopped, but respects the
+    /// memory's protection.
+    fn write_memory(handle: Self::Handle, address: usize, buf: &[u8]) -> io::Result<usize>;
+
+    /// Reads a stopped thread's general-purpose registers.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    fn get_regs(handle: Self::Handle) -> io::Result<user_regs_struct>;
+
+    /// Overwrites a stopped thread's general-purpose registers.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    fn set_regs(handle: Self::Handle, regs: user_regs_struct) -> io::Result<()>;
+
+    /// Reads a stopped thread's floating-point and SSE registers.
+    #[cfg(target_arch = "x86_64")]
+    fn get_fpregs(handle: Self::Handle) -> io::Result<user_fpregs_struct>;
+
+    /// Overwrites a stopped thread's floating-point and SSE registers.
+    #[cfg(target_arch = "x86_64")]
+    fn set_fpregs(handle: Self::Handle, regs: user_fpregs_struct) -> io::Result<()>;
+
+    /// Reads one of a stopped thread's debug registers (DR0-DR7).
+    #[cfg(target_arch = "x86_64")]
+    fn read_debug_register(handle: Self::Handle, register: usize) -> io::Result<usize>;
+
+    /// Overwrites one of a stopped thread's debug registers (DR0-DR7).
+    #[cfg(target_arch = "x86_64")]
+    fn write_debug_register(handle: Self::Handle, register: usize, value: usize) -> io::Result<()>;
+
+    /// Gets the base address of a process' memory.
+    fn get_base(handle: Self::Handle) -> io::Result<usize>;
+
+    /// Gets the full name of a process.
+    fn get_name(pid: Pid) -> io::Result<String>;
+}
+
+#[cfg(unix)]
+pub(crate) type Platform = crate::unix_utils::Unix;
+
+/// The active platform's handle to an attached process.
+pub(crate) type Handle = <Platform as Backend>::Handle;
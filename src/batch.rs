@@ -0,0 +1,95 @@
+use std::{
+    io::{self, ErrorKind},
+    mem::size_of,
+};
+
+use crate::{
+    backend::{Backend, Platform},
+    pod::{bytes_of, bytes_of_slice, from_bytes},
+    Pod, Process,
+};
+
+/// The most iovecs a single `process_vm_readv` call accepts.
+const IOV_MAX: usize = 1024;
+
+impl Process {
+    /// Reads a `T` from each of `addresses`, returning them in the same order.
+    ///
+    /// The reads are batched into as few `process_vm_readv` calls as
+    /// possible. Anything those can't read is retried one value at a time
+    /// with ptrace, and any error from that is returned.
+    pub fn read_many<T: Pod>(&mut self, addresses: &[usize]) -> io::Result<Vec<T>> {
+        let size = size_of::<T>();
+        if size == 0 {
+            return Ok(addresses.iter().map(|_| from_bytes(&[])).collect());
+        }
+
+        let mut data = vec![0; addresses.len() * size];
+        for (addresses, data) in addresses
+            .chunks(IOV_MAX)
+            .zip(data.chunks_mut(IOV_MAX * size))
+        {
+            let done = Platform::read_memory_many(self.handle, addresses, size, data).unwrap_or(0);
+
+            // process_vm_readv stops at the first address it can't read
+            for (i, &address) in addresses.iter().enumerate().skip(done / size) {
+                self.read_memory(address, &mut data[i * size..(i + 1) * size])?;
+            }
+        }
+
+        Ok(data.chunks_exact(size).map(from_bytes).collect())
+    }
+
+    /// Reads `count` consecutive `T`s, starting at `address`.
+    ///
+    /// Reads all of them at once. `address` doesn't need to be aligned.
+    pub fn read_array<T: Pod>(&mut self, address: usize, count: usize) -> io::Result<Vec<T>> {
+        let size = size_of::<T>();
+        if size == 0 {
+            return Ok((0..count).map(|_| from_bytes(&[])).collect());
+        }
+
+        let len = count.checked_mul(size).ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Can't read {count} values of {size} bytes each"),
+            )
+        })?;
+
+        let mut data = vec![0; len];
+        self.read_memory(address, &mut data)?;
+
+        Ok(data.chunks_exact(size).map(from_bytes).collect())
+    }
+
+    /// Writes each value in `writes` to its address, in order.
+    ///
+    /// The process is stopped once for the whole batch, so it can't run
+    /// between writes, then continued again if it was running before. If
+    /// stopping on writes is disabled (see `Process::set_auto_stop`), the
+    /// process is left alone. Stops at the first write that fails.
+    pub fn write_many<T: Pod>(&mut self, writes: &[(usize, T)]) -> io::Result<()> {
+        let was_stopped = self.stopped;
+        if self.auto_stop {
+            self.stop()?;
+        }
+
+        let result = writes
+            .iter()
+            .try_for_each(|(address, value)| self.write_all_at(*address, bytes_of(value)));
+
+        if self.auto_stop && !was_stopped {
+            self.cont()?;
+        }
+
+        result
+    }
+
+    /// Writes every value in `values` consecutively, starting at `address`.
+    ///
+    /// Writes them all in one go, like `Process::write_all_at`; bytes past the
+    /// end of the last value are left untouched.
+    pub fn write_array<T: Pod>(&mut self, address: usize, values: &[T]) -> io::Result<()> {
+        self.write_all_at(address, bytes_of_slice(values))
+    }
+}
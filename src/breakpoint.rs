@@ -0,0 +1,110 @@
+use std::io;
+
+use nix::sys::signal::Signal;
+
+use crate::{
+    backend::{Backend, Platform},
+    Process, WaitStatus,
+};
+
+/// The x86 `int3` instruction.
+const INT3: u8 = 0xcc;
+
+/// A software breakpoint set in a process.
+///
+/// Remembers the byte it replaced, so it can be restored with
+/// `Process::clear_breakpoint`. Created via `Process::set_breakpoint`. Only
+/// available on x86_64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Breakpoint {
+    address: usize,
+    original: u8,
+}
+
+impl Breakpoint {
+    /// Returns the address the breakpoint was set at.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Returns the byte the breakpoint replaced.
+    pub fn original(&self) -> u8 {
+        self.original
+    }
+}
+
+impl Process {
+    /// Sets a software breakpoint at `address`, by replacing the byte there
+    /// with `int3` (x86_64 only).
+    ///
+    /// The rest of the word containing `address` is left untouched.
+    pub fn set_breakpoint(&mut self, address: usize) -> io::Result<Breakpoint> {
+        let mut original = [0];
+        self.read_exact_at(address, &mut original)?;
+        self.write_all_at(address, &[INT3])?;
+
+        Ok(Breakpoint {
+            address,
+            original: original[0],
+        })
+    }
+
+    /// Clears a software breakpoint, restoring the byte it replaced.
+    pub fn clear_breakpoint(&mut self, bp: Breakpoint) -> io::Result<()> {
+        self.write_all_at(bp.address, &[bp.original])
+    }
+
+    /// Continues the process until it hits a breakpoint, and returns the
+    /// breakpoint's address (x86_64 only).
+    ///
+    /// Resumes and waits on the thread chosen with `Process::select_thread`
+    /// (the main thread by default), so only breakpoints hit by that thread
+    /// are caught. Stops the process first if it isn't already. Any other signal the
+    /// process stops with on the way is passed on to it. Once it traps, the
+    /// instruction pointer is moved back onto the `int3`, and the process is
+    /// left stopped there. To carry on past the breakpoint without losing it,
+    /// call `Process::step_over_breakpoint` before resuming again.
+    ///
+    /// Fails if the process exits first.
+    pub fn resume_to_breakpoint(&mut self) -> io::Result<usize> {
+        self.stop()?;
+
+        let tid = self.current_thread;
+        let thread = self.thread_handle();
+        let mut sig = None;
+        loop {
+            Platform::cont(thread, sig)?;
+            self.stopped = false;
+
+            match Platform::wait(thread)? {
+                WaitStatus::Stopped(_, Signal::SIGTRAP) => break,
+                WaitStatus::Stopped(_, other) => sig = Some(other),
+                WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                    return Err(io::Error::other(format!(
+                        "Thread {tid} exited before hitting a breakpoint"
+                    )));
+                }
+                _ => sig = None,
+            }
+        }
+        self.stopped = true;
+
+        // The trap is reported after `int3` executes, so rewind onto it
+        let address = self.instruction_pointer()? - 1;
+        self.set_instruction_pointer(address)?;
+
+        Ok(address)
+    }
+
+    /// Executes the instruction a breakpoint replaced, then re-arms the
+    /// breakpoint.
+    ///
+    /// The process must be stopped at the breakpoint, e.g. by
+    /// `Process::resume_to_breakpoint`. It's left stopped at the next
+    /// instruction.
+    pub fn step_over_breakpoint(&mut self, bp: &Breakpoint) -> io::Result<()> {
+        self.write_all_at(bp.address, &[bp.original])?;
+        self.step(None)?;
+        self.write_all_at(bp.address, &[INT3])
+    }
+}
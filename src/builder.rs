@@ -0,0 +1,122 @@
+use std::{io, marker::PhantomData};
+
+use nix::sys::ptrace;
+
+use crate::{
+    backend::{Backend, Handle, Platform},
+    ptrace_scope::attach_error,
+    Pid, Process,
+};
+
+/// Configures how to attach to a process.
+///
+/// `ProcessBuilder::new().build(pid)` is the same as `Process::new(pid)`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessBuilder {
+    seize: bool,
+    stop_on_attach: bool,
+    options: ptrace::Options,
+}
+
+impl ProcessBuilder {
+    /// Create a new builder with the default configuration: attach with
+    /// `PTRACE_ATTACH`, let the process keep running, and set no ptrace
+    /// options.
+    pub fn new() -> Self {
+        Self {
+            seize: false,
+            stop_on_attach: false,
+            options: ptrace::Options::empty(),
+        }
+    }
+
+    /// Sets whether to attach with `PTRACE_SEIZE` instead of `PTRACE_ATTACH`
+    /// (see `Process::seize`).
+    pub fn seize(mut self, seize: bool) -> Self {
+        self.seize = seize;
+        self
+    }
+
+    /// Sets whether to leave the process stopped once attached.
+    pub fn stop_on_attach(mut self, stop: bool) -> Self {
+        self.stop_on_attach = stop;
+        self
+    }
+
+    /// Sets the ptrace options to attach with (see `Process::set_options`).
+    pub fn options(mut self, options: ptrace::Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Attach to a process.
+    ///
+    /// Also reads its name from `/proc/<pid>/status`. If that fails, so will
+    /// the method. If ptrace isn't permitted to attach, fails with
+    /// `ErrorKind::PermissionDenied`, naming the ptrace scope (see
+    /// `ptrace_permitted`).
+    pub fn build(self, pid: Pid) -> io::Result<Process> {
+        // Call this first in case it fails
+        let name = Platform::get_name(pid)?;
+
+        self.build_with(pid, name, None)
+    }
+
+    /// Attach to a process, using an already-known name and base address
+    /// instead of reading them from `/proc` (see `Process::reattach`).
+    pub(crate) fn build_with(
+        self,
+        pid: Pid,
+        name: String,
+        base: Option<usize>,
+    ) -> io::Result<Process> {
+        let handle = self.attach(pid).map_err(|e| attach_error(pid, e))?;
+
+        Ok(Process {
+            pid,
+            handle,
+            stopped: self.stop_on_attach,
+            auto_stop: true,
+            detached: false,
+
+            attached_threads: Vec::new(),
+            current_thread: pid,
+
+            name,
+            base,
+            pointer_width: None,
+            regions_cache: None,
+
+            _thread_bound: PhantomData,
+        })
+    }
+
+    fn attach(&self, pid: Pid) -> io::Result<Handle> {
+        if self.seize {
+            let handle = Platform::seize(pid, self.options)?;
+            if self.stop_on_attach {
+                Platform::interrupt(handle)?;
+                Platform::wait(handle)?;
+            }
+
+            Ok(handle)
+        } else if self.stop_on_attach || !self.options.is_empty() {
+            let handle = Platform::attach_stopped(pid)?;
+            Platform::set_options(handle, self.options)?;
+
+            if !self.stop_on_attach {
+                Platform::cont(handle, None)?;
+            }
+
+            Ok(handle)
+        } else {
+            Platform::attach(pid)
+        }
+    }
+}
+
+impl Default for ProcessBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,48 @@
+use std::{io, mem::size_of};
+
+use crate::{pod::from_bytes, Pod, Process};
+
+/// A snapshot of a region of a process' memory, for reading many values
+/// out of it without a syscall each.
+///
+/// The snapshot is only as fresh as the last call to `CachedReader::refresh`;
+/// until then, it's all zeros.
+#[derive(Debug, Clone)]
+pub struct CachedReader {
+    address: usize,
+    data: Vec<u8>,
+}
+
+impl CachedReader {
+    /// Create a new cached reader for `length` bytes, starting at `address`.
+    ///
+    /// Doesn't read anything until `CachedReader::refresh` is called.
+    pub fn new(address: usize, length: usize) -> Self {
+        Self {
+            address,
+            data: vec![0; length],
+        }
+    }
+
+    /// Re-reads the whole region from the process.
+    pub fn refresh(&mut self, proc: &mut Process) -> io::Result<()> {
+        proc.read_exact_at(self.address, &mut self.data)
+    }
+
+    /// Decodes a `T` from the snapshot, `offset` bytes from the start.
+    ///
+    /// Panics if the value doesn't fit within the snapshot.
+    pub fn get<T: Pod>(&self, offset: usize) -> T {
+        from_bytes(&self.data[offset..offset + size_of::<T>()])
+    }
+
+    /// Returns the address the snapshot starts at.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Returns the raw bytes of the snapshot.
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::{self, ErrorKind};
+
+use iced_x86::{Decoder, DecoderOptions, Formatter, IntelFormatter};
+
+use crate::Process;
+
+/// The longest an x86 instruction can be.
+const MAX_INSTRUCTION_LEN: usize = 15;
+
+/// A single decoded x86 instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// The address of the instruction.
+    pub address: usize,
+    /// The length of the instruction, in bytes.
+    pub len: usize,
+    /// The mnemonic, e.g. `mov`.
+    pub mnemonic: String,
+    /// The whole instruction, in Intel syntax, e.g. `mov rax,[rbp-8]`.
+    pub text: String,
+}
+
+impl Process {
+    /// Decodes up to `count` x86 instructions, starting at `address`.
+    ///
+    /// Decoding stops early at the end of the mapping `address` is in, or at
+    /// the first invalid instruction. Uses 32- or 64-bit mode depending on
+    /// `Process::target_pointer_width`.
+    pub fn disassemble(&mut self, address: usize, count: usize) -> io::Result<Vec<Instruction>> {
+        let bitness = self.target_pointer_width()? as u32 * 8;
+
+        let region = self.which_region(address)?.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Address 0x{address:x} isn't mapped"),
+            )
+        })?;
+        let len = count
+            .saturating_mul(MAX_INSTRUCTION_LEN)
+            .min(region.end - address);
+        let code = self.read_vec(address, len)?;
+
+        let mut decoder = Decoder::with_ip(bitness, &code, address as u64, DecoderOptions::NONE);
+        let mut formatter = IntelFormatter::new();
+        let mut instructions = Vec::with_capacity(count);
+
+        while instructions.len() < count && decoder.can_decode() {
+            let instruction = decoder.decode();
+            if instruction.is_invalid() {
+                break;
+            }
+
+            let mut text = String::new();
+            formatter.format(&instruction, &mut text);
+            let mut mnemonic = String::new();
+            formatter.format_mnemonic(&instruction, &mut mnemonic);
+
+            instructions.push(Instruction {
+                address: instruction.ip() as usize,
+                len: instruction.len(),
+                mnemonic,
+                text,
+            });
+        }
+
+        Ok(instructions)
+    }
+}
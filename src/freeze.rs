@@ -0,0 +1,76 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    backend::{Backend, Platform},
+    pod::bytes_of,
+    Pod, Process,
+};
+
+/// How often a frozen value gets rewritten.
+const FREEZE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A value being continuously written into a process' memory.
+///
+/// Stops writing when dropped. Created via `Process::freeze`.
+#[derive(Debug)]
+pub struct FreezeHandle {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FreezeHandle {
+    /// Returns whether the value is still being written.
+    ///
+    /// This becomes `false` if a write fails (e.g. because the process exited).
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for FreezeHandle {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Process {
+    /// "Freezes" a value, by writing `value` to `address` every 10ms on a
+    /// background thread until the returned handle is dropped.
+    ///
+    /// Since only the attaching thread can use ptrace, the background thread
+    /// writes with `process_vm_writev` instead. This doesn't stop the process,
+    /// and can't write to read-only memory.
+    pub fn freeze<T: Pod + Send>(&mut self, address: usize, value: T) -> FreezeHandle {
+        let handle = self.handle;
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let running = running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    if Platform::write_memory(handle, address, bytes_of(&value)).is_err() {
+                        running.store(false, Ordering::Relaxed);
+                        break;
+                    }
+
+                    thread::sleep(FREEZE_INTERVAL);
+                }
+            })
+        };
+
+        FreezeHandle {
+            running,
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+};
+
+use crate::Process;
+
+/// A guard that keeps a process stopped while it's alive.
+///
+/// Stops the process when created, and continues it when dropped (unless
+/// it was already stopped beforehand). Created via `Process::stopped_guard`.
+///
+/// Can be dereferenced to the underlying `Process`.
+#[derive(Debug)]
+pub struct StopGuard<'a> {
+    proc: &'a mut Process,
+
+    was_stopped: bool,
+}
+
+impl<'a> StopGuard<'a> {
+    /// Create a new stop guard, stopping the process.
+    pub fn new(proc: &'a mut Process) -> io::Result<Self> {
+        let was_stopped = proc.stopped;
+        proc.stop()?;
+
+        Ok(Self { proc, was_stopped })
+    }
+}
+
+impl<'a> Deref for StopGuard<'a> {
+    type Target = Process;
+
+    fn deref(&self) -> &Self::Target {
+        self.proc
+    }
+}
+
+impl<'a> DerefMut for StopGuard<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.proc
+    }
+}
+
+impl<'a> Drop for StopGuard<'a> {
+    fn drop(&mut self) {
+        if self.was_stopped {
+            return;
+        }
+
+        if let Err(e) = self.proc.cont() {
+            if !std::thread::panicking() {
+                panic!(
+                    "Failed to continue process {} after stop guard was dropped: {e}",
+                    self.pid
+                );
+            }
+        }
+    }
+}
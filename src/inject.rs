@@ -0,0 +1,167 @@
+use std::io::{self, ErrorKind};
+
+use nix::{errno::Errno, libc};
+
+use crate::{Perms, Process};
+
+/// The x86_64 `syscall` instruction.
+const SYSCALL: [u8; 2] = [0x0f, 0x05];
+
+/// How many bytes to read at a time while searching for a `syscall` instruction.
+const SEARCH_CHUNK: usize = 0x1000;
+
+impl Process {
+    /// Finds a `syscall` instruction somewhere in the process' executable memory.
+    ///
+    /// If the process was stopped inside a syscall, `rip` points just past the
+    /// instruction that made it, so that's checked first.
+    fn find_syscall_instruction(&mut self, rip: usize) -> io::Result<usize> {
+        let mut buf = [0; SEARCH_CHUNK];
+        if let Some(address) = rip.checked_sub(SYSCALL.len()) {
+            if self.read_exact_at(address, &mut buf[..2]).is_ok() && buf[..2] == SYSCALL {
+                return Ok(address);
+            }
+        }
+
+        for region in self.regions()? {
+            if !(region.perms.read && region.perms.exec) {
+                continue;
+            }
+
+            let mut address = region.start;
+            while address + 1 < region.end {
+                let len = SEARCH_CHUNK.min(region.end - address);
+                self.read_exact_at(address, &mut buf[..len])?;
+
+                if let Some(i) = buf[..len].windows(2).position(|bytes| bytes == SYSCALL) {
+                    return Ok(address + i);
+                }
+
+                // Overlap by a byte, in case the instruction straddles two chunks
+                address += len - 1;
+            }
+        }
+
+        Err(io::Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "Failed to find a syscall instruction in process {}",
+                self.pid
+            ),
+        ))
+    }
+
+    /// Makes the process execute a syscall, returning its result.
+    ///
+    /// Uses the x86_64 syscall ABI: the number goes in `rax`, and the arguments
+    /// in `rdi`, `rsi`, `rdx`, `r10`, `r8` and `r9`. Stops the process, and
+    /// restores its registers afterwards.
+    pub(crate) fn remote_syscall(
+        &mut self,
+        number: libc::c_long,
+        args: [u64; 6],
+    ) -> io::Result<u64> {
+        let saved = self.get_regs()?;
+        let address = self.find_syscall_instruction(saved.rip as usize)?;
+
+        let mut regs = saved;
+        regs.rax = number as u64;
+        // Stops the kernel from trying to restart whatever syscall the process
+        // was stopped in, which would clobber `rax` and `rip`
+        regs.orig_rax = u64::MAX;
+        regs.rdi = args[0];
+        regs.rsi = args[1];
+        regs.rdx = args[2];
+        regs.r10 = args[3];
+        regs.r8 = args[4];
+        regs.r9 = args[5];
+        regs.rip = address as u64;
+
+        self.set_regs(regs)?;
+        let result = self.step(None).and_then(|_| self.get_regs());
+        self.set_regs(saved)?;
+
+        let ret = result?.rax as i64;
+        if (-4095..0).contains(&ret) {
+            return Err(Errno::from_i32(-ret as i32).into());
+        }
+
+        Ok(ret as u64)
+    }
+
+    /// Allocates `size` bytes of memory in the process (rounded up to whole
+    /// pages), with the given permissions. Returns the address of the new
+    /// memory.
+    ///
+    /// This works by making the process call `mmap` itself (with
+    /// `MAP_PRIVATE | MAP_ANONYMOUS`), using the x86_64 syscall number and
+    /// calling convention. The process is stopped, and its registers are
+    /// restored afterwards. Fails with `ErrorKind::InvalidInput` if rounding
+    /// `size` up overflows.
+    pub fn alloc(&mut self, size: usize, prot: Perms) -> io::Result<usize> {
+        let size = size
+            .checked_next_multiple_of(self.page_size())
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid size: 0x{size:x} can't be rounded up to whole pages"),
+                )
+            })?;
+        let address = self.remote_syscall(
+            libc::SYS_mmap,
+            [
+                0,
+                size as u64,
+                prot.prot() as u64,
+                (libc::MAP_PRIVATE | libc::MAP_ANONYMOUS) as u64,
+                u64::MAX,
+                0,
+            ],
+        )?;
+        self.regions_cache = None;
+
+        Ok(address as usize)
+    }
+
+    /// Changes the permissions of `len` bytes of memory at `address`. Since
+    /// permissions apply to whole pages, the range is widened to cover every
+    /// page it touches.
+    ///
+    /// This works by making the process call `mprotect` itself, the same way
+    /// as `Process::alloc`. Restoring the original permissions is up to you,
+    /// so this returns the permissions the mapping containing `address` had
+    /// before. Fails with `ErrorKind::InvalidInput` if `address` isn't mapped,
+    /// or the range runs off the end of the address space.
+    pub fn protect(&mut self, address: usize, len: usize, prot: Perms) -> io::Result<Perms> {
+        let page_size = self.page_size();
+        let start = address - address % page_size;
+        let end = address
+            .checked_add(len)
+            .and_then(|end| end.checked_next_multiple_of(page_size))
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid range: 0x{address:x} + 0x{len:x} is out of range"),
+                )
+            })?;
+
+        let original = self
+            .which_region(address)?
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Address 0x{address:x} isn't mapped"),
+                )
+            })?
+            .perms;
+
+        let len = end - start;
+        self.remote_syscall(
+            libc::SYS_mprotect,
+            [start as u64, len as u64, prot.prot() as u64, 0, 0, 0],
+        )?;
+        self.regions_cache = None;
+
+        Ok(original)
+    }
+}
@@ -1,29 +1,84 @@
 use std::{
-    fs::{read_dir, read_to_string},
-    io::{self, ErrorKind, Read},
-    os::raw::c_void,
-    ptr::null,
+    fs::{read, read_dir, read_link, read_to_string, File},
+    io::{self, ErrorKind, Read, Write},
+    marker::PhantomData,
+    path::PathBuf,
+    thread::sleep,
+    time::{Duration, Instant},
 };
 
-use nix::sys::{
-    ptrace,
-    signal::{self, Signal},
-    wait::waitpid,
+use nix::{
+    sys::{ptrace, signal::Signal},
+    unistd::{sysconf, SysconfVar},
 };
 
-pub use nix::{errno::Errno, unistd::Pid};
+pub use nix::{errno::Errno, sys::wait::WaitStatus, unistd::Pid};
 
+use backend::{Backend, Handle, Platform};
+
+pub mod address;
+mod backend;
+mod batch;
+#[cfg(target_arch = "x86_64")]
+pub mod breakpoint;
+pub mod builder;
+pub mod cache;
+#[cfg(feature = "disasm")]
+pub mod disasm;
+pub mod freeze;
+pub mod guard;
+#[cfg(target_arch = "x86_64")]
+mod inject;
+pub mod maps;
+pub mod patch;
+pub mod pod;
+pub mod pointer_map;
+pub mod ptrace_scope;
 pub mod reader;
+pub mod regs;
+pub mod scan;
+pub mod snapshot;
+pub mod stat;
+pub mod symbols;
+#[cfg(unix)]
+mod unix_utils;
+pub mod watchpoint;
 pub mod writer;
 
-pub use reader::ProcessReader;
+pub use address::Address;
+#[cfg(target_arch = "x86_64")]
+pub use breakpoint::Breakpoint;
+pub use builder::ProcessBuilder;
+pub use cache::CachedReader;
+#[cfg(feature = "disasm")]
+pub use disasm::Instruction;
+pub use freeze::FreezeHandle;
+pub use guard::StopGuard;
+pub use maps::{MemoryRegion, Perms};
+pub use patch::{Patch, PatchGuard};
+pub use pod::Pod;
+pub use pointer_map::PointerMap;
+pub use ptrace_scope::{ptrace_permitted, PtraceScope};
+pub use reader::{ProcessReader, ReadMode};
+#[cfg(target_arch = "x86_64")]
+pub use regs::user_fpregs_struct;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub use regs::{user_regs_struct, Registers};
+pub use scan::{Distance, RegionFilter, ScanCompare, ScanOptions};
+pub use snapshot::{ChangeKind, Snapshot};
+pub use stat::ProcStat;
+pub use watchpoint::{WatchKind, WatchLen, Watchpoint};
 pub use writer::ProcessWriter;
 
-const POINTER_WIDTH: usize = usize::BITS as usize / 8;
+/// How often `wait_for_process` checks for the process.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 fn get_process_status_name(file: &str) -> io::Result<String> {
     let data = read_to_string(file)?;
-    let line = data.lines().next().expect("Bad /proc/*/status format");
+    let line = data
+        .lines()
+        .next()
+        .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, format!("{file} is empty")))?;
     if let Some(name) = line.strip_prefix("Name:\t") {
         return Ok(name.to_string());
     }
@@ -34,12 +89,152 @@ fn get_process_status_name(file: &str) -> io::Result<String> {
     ))
 }
 
-fn check_process_status_file(file: &str, target: &str) -> io::Result<bool> {
-    Ok(get_process_status_name(file)?.contains(target))
+/// Returns whether an error reading a process' files from `/proc` means it
+/// exited in the meantime.
+///
+/// A process that exits while its status is being read can leave the file
+/// empty, which `get_process_status_name` reports as `ErrorKind::InvalidData`.
+fn process_vanished(e: &io::Error) -> bool {
+    matches!(e.kind(), ErrorKind::NotFound | ErrorKind::InvalidData)
+        || e.raw_os_error() == Some(nix::libc::ESRCH)
+}
+
+/// Returns an iterator over every process in `/proc`, as pairs of pid and name.
+///
+/// Processes that exit while `/proc` is being read are skipped (see
+/// `process_vanished`).
+fn processes() -> io::Result<impl Iterator<Item = io::Result<(Pid, String)>>> {
+    Ok(read_dir("/proc")?.filter_map(|entry| {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => return Some(Err(e)),
+        };
+        let pid = entry.file_name().to_str()?.parse().ok()?;
+
+        match get_process_status_name(&format!("/proc/{pid}/status")) {
+            Ok(name) => Some(Ok((Pid::from_raw(pid), name))),
+            Err(e) if process_vanished(&e) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }))
+}
+
+/// Finds the pid of a process by name, without attaching to it. Simply checks for string
+/// inclusion, like `Process::find`.
+pub fn pid_of(name: &str) -> io::Result<Option<Pid>> {
+    for process in processes()? {
+        let (pid, process_name) = process?;
+        if process_name.contains(name) {
+            return Ok(Some(pid));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Finds the pid of a process by name, without attaching to it. Only allows strict matches,
+/// like `Process::find_strict`.
+pub fn pid_of_strict(name: &str) -> io::Result<Option<Pid>> {
+    for process in processes()? {
+        let (pid, process_name) = process?;
+        if process_name == name {
+            return Ok(Some(pid));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Waits for a process whose name contains `name` to appear, then returns its
+/// pid, without attaching to it. Matches like `pid_of`.
+///
+/// Polls `/proc` every 50 milliseconds. If `timeout` is given and runs
+/// out first, fails with `ErrorKind::TimedOut`.
+pub fn wait_for_process(name: &str, timeout: Option<Duration>) -> io::Result<Pid> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        if let Some(pid) = pid_of(name)? {
+            return Ok(pid);
+        }
+
+        let mut interval = WAIT_POLL_INTERVAL;
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(io::Error::new(
+                    ErrorKind::TimedOut,
+                    format!("Timed out waiting for process `{name}`"),
+                ));
+            }
+
+            interval = interval.min(remaining);
+        }
+
+        sleep(interval);
+    }
+}
+
+/// Lists every running process, as pairs of pid and name, sorted by pid.
+///
+/// Processes whose status can't be read (e.g. because they exited while
+/// `/proc` was being read) are skipped.
+pub fn list_processes() -> io::Result<Vec<(Pid, String)>> {
+    let mut processes: Vec<_> = processes()?.flatten().collect();
+
+    processes.sort_by_key(|(pid, _)| pid.as_raw());
+    Ok(processes)
+}
+
+/// Adds `offset` to `base`, failing with `ErrorKind::InvalidInput` instead of
+/// wrapping around.
+pub(crate) fn offset_address(base: usize, offset: isize) -> io::Result<usize> {
+    base.checked_add_signed(offset).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid address: 0x{base:x} {offset:+} is out of range"),
+        )
+    })
+}
+
+/// Splits the contents of `/proc/<pid>/cmdline` into arguments.
+///
+/// Each argument ends with a NUL, so empty arguments show up as two NULs in a
+/// row. Only the final terminator is stripped, to keep them.
+fn split_cmdline(data: &[u8]) -> Vec<String> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    data.strip_suffix(&[0])
+        .unwrap_or(data)
+        .split(|byte| *byte == 0)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect()
+}
+
+/// Strips the trailing NULs from a fixed-width string.
+fn trim_nul_padding(data: &[u8]) -> &[u8] {
+    let end = data
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+
+    &data[..end]
 }
 
-fn check_process_status_file_strict(file: &str, target: &str) -> io::Result<bool> {
-    Ok(get_process_status_name(file)? == target)
+fn follow_chain(
+    proc: &mut Process,
+    pointer_width: usize,
+    mut address: usize,
+    offsets: &[isize],
+) -> io::Result<usize> {
+    for offset in offsets.iter() {
+        let pointer = proc.read_pointer(address, pointer_width)?;
+        address = offset_address(pointer, *offset)?;
+    }
+
+    Ok(address)
 }
 
 /// An attached process.
@@ -51,193 +246,429 @@ fn check_process_status_file_strict(file: &str, target: &str) -> io::Result<bool
 ///
 /// Modifying a process' memory stops the process. To continue it, use `Process::cont()`,
 /// or detach. Reading does not stop the process; you must stop it yourself.
+///
+/// On Linux, only the thread that attached to a process is allowed to trace
+/// it; ptrace calls from any other thread fail with `ESRCH`. Because of this,
+/// `Process` is neither `Send` nor `Sync`.
 #[derive(Debug)]
 pub struct Process {
     pid: Pid,
+    /// What the backend knows the process by.
+    handle: Handle,
     stopped: bool,
+    auto_stop: bool,
+    /// Set once the struct has detached (or killed the process), so dropping
+    /// it doesn't try again.
+    detached: bool,
+
+    /// Threads attached with `Process::attach_thread`, and the backend's
+    /// handles to them.
+    attached_threads: Vec<(Pid, Handle)>,
+    /// The thread that register access and stepping act on.
+    current_thread: Pid,
 
     name: String,
     base: Option<usize>,
+    pointer_width: Option<usize>,
+    /// Regions cached by `Process::is_readable` and `Process::is_writable`.
+    regions_cache: Option<(Instant, Vec<MemoryRegion>)>,
+
+    _thread_bound: PhantomData<*const ()>,
 }
 
 impl Process {
     /// Attach to a process.
     ///
+    /// To configure how to attach, use `ProcessBuilder`.
+    ///
     /// Also reads its name from `/proc/<pid>/status`. If that fails, so will
     /// the method.
     pub fn new(pid: Pid) -> io::Result<Self> {
-        // Call this first in case it fails
-        let name = get_process_status_name(&format!("/proc/{pid}/status"))?;
-
-        ptrace::attach(pid)?;
-        waitpid(pid, None)?;
-        ptrace::cont(pid, None)?;
-        waitpid(pid, None)?;
+        ProcessBuilder::new().build(pid)
+    }
 
-        Ok(Self {
-            pid,
-            stopped: false,
+    /// Attach to a process using `PTRACE_SEIZE`.
+    ///
+    /// Unlike `Process::new`, this doesn't stop the process at all while
+    /// attaching, and doesn't change how it receives signals. A seized process
+    /// can be interrupted with `PTRACE_INTERRUPT` (or `Process::stop`).
+    ///
+    /// Also reads its name from `/proc/<pid>/status`. If that fails, so will
+    /// the method.
+    pub fn seize(pid: Pid) -> io::Result<Self> {
+        ProcessBuilder::new().seize(true).build(pid)
+    }
 
-            name,
-            base: None,
-        })
+    /// Attach to a process again after detaching from it, reusing its name and
+    /// (if known) base address instead of reading them from `/proc`.
+    ///
+    /// Useful for tools that periodically detach to let the process run
+    /// undisturbed. Get the parts with `Process::name` and `Process::base`
+    /// before detaching. If the process has since called `execve`, the cached
+    /// base will be stale; pass `None` to have it read again when needed.
+    pub fn reattach(pid: Pid, name: String, base: Option<usize>) -> io::Result<Self> {
+        ProcessBuilder::new().build_with(pid, name, base)
     }
 
     /// Finds a process by name, then calls `Process::new`. Simply checks for string inclusion (e.g.
     /// `myapp` will match both `./myapp --gui` and `find / | grep myapp`, whichever has a lower pid).
     pub fn find(target: &str) -> io::Result<Self> {
-        let dir = read_dir("/proc")?;
-
-        for entry in dir {
-            let entry = entry?;
-            if !entry
-                .file_name()
-                .to_string_lossy()
-                .chars()
-                .all(char::is_numeric)
-            {
-                continue;
-            }
-
-            if check_process_status_file(
-                &format!("/proc/{}/status", entry.file_name().to_string_lossy()),
-                target,
-            )? {
-                return Self::new(Pid::from_raw(
-                    entry.file_name().to_string_lossy().parse().unwrap(),
-                ));
-            }
+        match pid_of(target)? {
+            Some(pid) => Self::new(pid),
+            None => Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find process `{target}`"),
+            )),
         }
-
-        Err(io::Error::new(
-            ErrorKind::NotFound,
-            format!("Failed to find process `{target}`"),
-        ))
     }
 
     /// Finds a process by name, then calls `Process::new`. Only allows strict matches (e.g.
     /// `myapp` won't match `./myapp --gui` and `find / | grep myapp`).
     pub fn find_strict(target: &str) -> io::Result<Self> {
-        let dir = read_dir("/proc")?;
-
-        for entry in dir {
-            let entry = entry?;
-            if !entry
-                .file_name()
-                .to_string_lossy()
-                .chars()
-                .all(char::is_numeric)
-            {
-                continue;
-            }
+        match pid_of_strict(target)? {
+            Some(pid) => Self::new(pid),
+            None => Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find process `{target}`"),
+            )),
+        }
+    }
 
-            if check_process_status_file_strict(
-                &format!("/proc/{}/status", entry.file_name().to_string_lossy()),
-                target,
-            )? {
-                return Self::new(Pid::from_raw(
-                    entry.file_name().to_string_lossy().parse().unwrap(),
-                ));
+    /// Finds every process whose name includes `target`, in the same way as `Process::find`.
+    ///
+    /// Doesn't attach to any of them; use `Process::new` on the pid you want.
+    pub fn find_all(target: &str) -> io::Result<Vec<Pid>> {
+        let mut pids = Vec::new();
+        for process in processes()? {
+            let (pid, name) = process?;
+            if name.contains(target) {
+                pids.push(pid);
             }
         }
 
-        Err(io::Error::new(
-            ErrorKind::NotFound,
-            format!("Failed to find process `{target}`"),
-        ))
+        Ok(pids)
+    }
+
+    /// Forgets the cached base address (along with the pointer width and
+    /// regions), so the next call that needs it reads /proc/pid/maps again.
+    ///
+    /// Use this after the process calls `execve`. `Process::wait` does this
+    /// automatically when it reports an exec (see `Process::set_options`).
+    pub fn invalidate_base(&mut self) {
+        self.base = None;
+        self.pointer_width = None;
+        self.regions_cache = None;
     }
 
     /// Gets the base address of the process' memory (the first mapping in /proc/pid/maps).
     ///
     /// If it hasn't been called yet, calling `<read/write>_word_offset` will call this first.
     pub fn get_base(&mut self) -> io::Result<()> {
-        if self.base.is_some() {
-            return Ok(());
+        if self.base.is_none() {
+            self.base = Some(Platform::get_base(self.handle)?);
         }
 
-        let file = format!("/proc/{}/maps", self.pid);
-
-        let data = read_to_string(file)?;
-		let line = data.lines().next().ok_or(Errno::ENOKEY)?;
-		let (base, _) = line.split_once('-').ok_or(Errno::ENOKEY)?;
-        self.base = Some(usize::from_str_radix(base, 16).map_err(|_| {
-            io::Error::new(
-                ErrorKind::InvalidData,
-                format!("Bad format in /proc/{}/maps", self.pid),
-            )
-        })?);
-        
         Ok(())
     }
 
+    /// Returns the size of a pointer in the process, in bytes (4 for 32-bit
+    /// processes, 8 for 64-bit ones).
+    ///
+    /// Worked out from the ELF header of `/proc/<pid>/exe` the first time it's
+    /// called, then cached.
+    pub fn target_pointer_width(&mut self) -> io::Result<usize> {
+        if let Some(pointer_width) = self.pointer_width {
+            return Ok(pointer_width);
+        }
+
+        let mut header = [0; 5];
+        File::open(format!("/proc/{}/exe", self.pid))?.read_exact(&mut header)?;
+
+        // `EI_CLASS` is 1 for 32-bit, and 2 for 64-bit
+        let pointer_width = match header {
+            [0x7f, b'E', b'L', b'F', 1] => 4,
+            [0x7f, b'E', b'L', b'F', 2] => 8,
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Bad ELF header in /proc/{}/exe", self.pid),
+                ))
+            }
+        };
+
+        self.pointer_width = Some(pointer_width);
+        Ok(pointer_width)
+    }
+
+    /// Overrides the pointer width `Process::target_pointer_width` reports,
+    /// so 32-bit pointer handling can be tested against a 64-bit process.
+    #[doc(hidden)]
+    pub fn set_target_pointer_width(&mut self, pointer_width: usize) {
+        self.pointer_width = Some(pointer_width);
+    }
+
     /// Halts the process.
     ///
     /// Called before all read/write operations.
     pub fn stop(&mut self) -> io::Result<()> {
         if !self.stopped {
-            signal::kill(self.pid, Signal::SIGSTOP)?;
-            waitpid(self.pid, None)?;
+            Platform::stop(self.handle)?;
             self.stopped = true;
         }
 
         Ok(())
     }
 
+    /// Sets whether writes stop the process first. Enabled by default.
+    ///
+    /// This affects `Process::write_word` (and everything built on it, like
+    /// `ProcessWriter::flush`). When disabled, you're responsible for making
+    /// sure the process is stopped before writing, or writes race it; see
+    /// `Process::write_word_nostop` for the hazards.
+    pub fn set_auto_stop(&mut self, enabled: bool) {
+        self.auto_stop = enabled;
+    }
+
+    /// Returns whether writes stop the process first.
+    pub fn auto_stop(&self) -> bool {
+        self.auto_stop
+    }
+
+    /// Returns whether the process is stopped, as far as this struct knows.
+    ///
+    /// This only tracks stops and continues made through `Process` (e.g.
+    /// `Process::stop`, `Process::cont`, and `Process::wait`). If something
+    /// else stops or continues the process, this won't notice.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Stops the process, returning a `StopGuard` that continues it again when dropped.
+    ///
+    /// If the process was already stopped, the guard leaves it stopped.
+    pub fn stopped_guard(&mut self) -> io::Result<StopGuard<'_>> {
+        StopGuard::new(self)
+    }
+
     /// Continues the process.
     ///
     /// This is never called automatically.
     pub fn cont(&mut self) -> io::Result<()> {
         if self.stopped {
-            signal::kill(self.pid, Signal::SIGCONT)?;
+            // A traced process stays in its ptrace-stop until the tracer
+            // resumes it; `SIGCONT` alone won't do it
+            Platform::cont(self.handle, None)?;
             self.stopped = false;
         }
 
         Ok(())
     }
 
+    /// Continues the process, delivering `sig` to it as it resumes.
+    ///
+    /// Use this to pass a signal the process stopped with (see
+    /// `Process::wait`) on to its own handler, e.g. a `SIGSEGV`. Unlike
+    /// `Process::cont`, this doesn't check whether the process is stopped;
+    /// it must be in a ptrace-stop.
+    pub fn cont_with(&mut self, sig: Signal) -> io::Result<()> {
+        Platform::cont(self.handle, Some(sig))?;
+        self.stopped = false;
+
+        Ok(())
+    }
+
+    /// Continues the process until its next syscall entry or exit
+    /// (`PTRACE_SYSCALL`), delivering `sig` to it as it resumes.
+    ///
+    /// Doesn't wait for the stop; call `Process::wait` for that. Stops
+    /// alternate between entry and exit, starting with an entry, so call this
+    /// twice per syscall. At entry, the syscall number and arguments can be
+    /// read with `Process::get_regs` (`orig_rax`, `rdi`, `rsi`, etc. on
+    /// x86_64); at exit, so can the return value (`rax`). With
+    /// `PTRACE_O_TRACESYSGOOD` set (see `Process::set_options`), these stops
+    /// are reported as `WaitStatus::PtraceSyscall`, otherwise as a `SIGTRAP`.
+    ///
+    /// Like `Process::cont_with`, the process must be in a ptrace-stop.
+    pub fn syscall(&mut self, sig: Option<Signal>) -> io::Result<()> {
+        Platform::syscall(self.handle, sig)?;
+        self.stopped = false;
+
+        Ok(())
+    }
+
+    /// Executes a single instruction, then waits for the process to stop again.
+    ///
+    /// Steps the thread chosen with `Process::select_thread` (the main thread
+    /// by default). The process must already be stopped. If `sig` is given, it's delivered
+    /// to the process as it resumes.
+    pub fn step(&mut self, sig: Option<Signal>) -> io::Result<()> {
+        let thread = self.thread_handle();
+        Platform::step(thread, sig)?;
+        Platform::wait(thread)?;
+        self.stopped = true;
+
+        Ok(())
+    }
+
+    /// Waits for the process to change state, and returns why it did.
+    ///
+    /// This is how you find out whether the process stopped because of a
+    /// signal (e.g. `SIGSEGV`), a trap (e.g. a breakpoint, which shows up as
+    /// `SIGTRAP`), or because it exited. Blocks until something happens.
+    ///
+    /// If the process called `execve` (and `PTRACE_O_TRACEEXEC` is set), the
+    /// cached base address is invalidated.
+    pub fn wait(&mut self) -> io::Result<WaitStatus> {
+        let status = Platform::wait(self.handle)?;
+        if let WaitStatus::PtraceEvent(_, _, event) = status {
+            if event == ptrace::Event::PTRACE_EVENT_EXEC as i32 {
+                self.invalidate_base();
+            }
+        }
+
+        match status {
+            WaitStatus::Stopped(..)
+            | WaitStatus::PtraceEvent(..)
+            | WaitStatus::PtraceSyscall(..) => self.stopped = true,
+            WaitStatus::Continued(..) | WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                self.stopped = false
+            }
+            WaitStatus::StillAlive => {}
+        }
+
+        Ok(status)
+    }
+
+    /// Sets the ptrace options for the process (`PTRACE_SETOPTIONS`).
+    ///
+    /// The process must be stopped. The options replace any set before.
+    ///
+    /// Since `Process::new` attaches with `PTRACE_ATTACH`, the useful options
+    /// are the `PTRACE_O_TRACE*` family (`TRACEFORK`, `TRACEVFORK`,
+    /// `TRACECLONE`, `TRACEEXEC`, `TRACEEXIT`, `TRACESYSGOOD`), which make
+    /// `Process::wait` report `WaitStatus::PtraceEvent`/`PtraceSyscall`, and
+    /// `PTRACE_O_EXITKILL`. Group-stop reporting (`PTRACE_EVENT_STOP`) only
+    /// works for processes attached with `Process::seize`.
+    pub fn set_options(&mut self, options: ptrace::Options) -> io::Result<()> {
+        Platform::set_options(self.handle, options)
+    }
+
     /// Detaches from the process.
-    /// 
+    ///
     /// This consumes the struct.
     pub fn detach(mut self) -> io::Result<()> {
+        self.detached = true;
         self.detach_without_consuming()
     }
 
+    /// Detaches from the process, but leaves it stopped.
+    ///
+    /// Plain `Process::detach` resumes the process. This instead stops it
+    /// (if it isn't already) and detaches with `SIGSTOP`, so it stays in
+    /// group-stop until something sends it `SIGCONT`. Useful for handing a
+    /// frozen process over to another tool.
+    ///
+    /// This consumes the struct.
+    pub fn detach_stopped(mut self) -> io::Result<()> {
+        self.stop()?;
+        self.detached = true;
+
+        for (_, thread) in std::mem::take(&mut self.attached_threads) {
+            Platform::detach(thread, None)?;
+        }
+
+        Platform::detach(self.handle, Some(Signal::SIGSTOP))
+    }
+
+    /// Kills the process with `SIGKILL`, then waits for it to die.
+    ///
+    /// This consumes the struct; there's nothing left to detach from.
+    pub fn kill(mut self) -> io::Result<()> {
+        self.detached = true;
+
+        Platform::kill(self.handle)
+    }
+
     fn detach_without_consuming(&mut self) -> io::Result<()> {
-        let sig = if self.stopped {
-            Some(Signal::SIGCONT)
-        } else {
-            None
-        };
+        for (_, thread) in std::mem::take(&mut self.attached_threads) {
+            Platform::detach(thread, None)?;
+        }
+
+        // ptrace can only detach from a stopped tracee
+        let was_stopped = self.stopped;
+        self.stop()?;
 
-        ptrace::detach(self.pid, sig).map_err(Errno::into)
+        // Undo any stop the process was in before `Process::stop`
+        Platform::detach(self.handle, was_stopped.then_some(Signal::SIGCONT))
     }
 
     /// Reads a single word from the process' memory.
     pub fn read_word(&mut self, address: usize) -> io::Result<isize> {
-        let addr = unsafe { null::<c_void>().add(address) as *mut c_void };
+        Platform::read_word(self.handle, address)
+    }
+
+    /// Reads `buf.len()` bytes from the process' memory, starting at `address`.
+    ///
+    /// Uses a single `process_vm_readv` call where possible, and falls back to
+    /// reading word-by-word with ptrace for anything it couldn't read.
+    fn read_memory(&mut self, address: usize, buf: &mut [u8]) -> io::Result<()> {
+        let done = Platform::read_memory(self.handle, address, buf).unwrap_or(0);
 
-        let data = ptrace::read(self.pid, addr)? as isize;
-        Ok(data)
+        for i in (done..buf.len()).step_by(8) {
+            let word = self.read_word(address + i)?;
+
+            for j in 0..8.min(buf.len() - i) {
+                buf[i + j] = (word >> (j * 8)) as u8;
+            }
+        }
+
+        Ok(())
     }
 
     /// Reads a single word from the process' memory, using `offset`.
     ///
     /// If `Process::get_base()` hasn't been called yet, calls that first.
     pub fn read_word_offset(&mut self, offset: usize) -> io::Result<isize> {
-        self.get_base()?;
-        self.read_word(self.base.unwrap() + offset)
+        let address = self.checked_base_offset(offset)?;
+        self.read_word(address)
     }
 
     /// Writes a single word into the process' memory.
+    ///
+    /// Stops the process first, unless that's been disabled with
+    /// `Process::set_auto_stop`. In that case, a process that isn't stopped is
+    /// written to while it runs, like `Process::write_word_nostop`.
     pub fn write_word(&mut self, address: usize, data: isize) -> io::Result<()> {
-        self.stop()?;
-
-        let addr = unsafe { null::<c_void>().add(address) as *mut c_void };
+        if self.auto_stop {
+            self.stop()?;
+        } else if !self.stopped {
+            return self.write_word_nostop(address, data);
+        }
 
-        let data = unsafe { null::<c_void>().offset(data) as *mut c_void };
+        Platform::write_word(self.handle, address, data)
+    }
 
-        unsafe {
-            ptrace::write(self.pid, addr, data)?;
+    /// Writes a single word into the process' memory, *without* stopping it
+    /// first.
+    ///
+    /// ***This is dangerous!*** The process keeps running while you write,
+    /// so it may read a half-updated value, or overwrite yours right after.
+    /// Only use this if you're deliberately racing the process and accept the
+    /// consequences.
+    ///
+    /// Unlike `Process::write_word`, this can't bypass the memory's
+    /// protection, so writing to read-only memory (such as code) fails.
+    pub fn write_word_nostop(&mut self, address: usize, data: isize) -> io::Result<()> {
+        let bytes = data.to_ne_bytes();
+        let written = Platform::write_memory(self.handle, address, &bytes)?;
+        if written != bytes.len() {
+            return Err(io::Error::new(
+                ErrorKind::WriteZero,
+                format!(
+                    "Only wrote {written} of {} bytes at 0x{address:x}",
+                    bytes.len()
+                ),
+            ));
         }
 
         Ok(())
@@ -247,28 +678,173 @@ impl Process {
     ///
     /// If `Process::get_base()` hasn't been called yet, calls that first.
     pub fn write_word_offset(&mut self, offset: usize, data: isize) -> io::Result<()> {
-        self.get_base()?;
-        self.write_word(self.base.unwrap() + offset, data)
+        let address = self.checked_base_offset(offset)?;
+        self.write_word(address, data)
+    }
+
+    /// Reads exactly `buf.len()` bytes from the process' memory, starting at `address`.
+    ///
+    /// If the read runs into unmapped memory, the error names the first
+    /// unmapped address.
+    pub fn read_exact_at(&mut self, address: usize, buf: &mut [u8]) -> io::Result<()> {
+        self.reader(address, buf.len())?.read_exact(buf)
+    }
+
+    /// Reads `buf.len()` bytes from the process' memory at `address`,
+    /// returning how many were read.
+    ///
+    /// A one-shot alternative to a non-advancing `ProcessReader`. Works for
+    /// any length, including ones that aren't a multiple of the word size.
+    pub fn peek(&mut self, address: usize, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_memory(address, buf)?;
+        Ok(buf.len())
+    }
+
+    /// Reads `len` bytes from the process' memory, starting at `address`.
+    pub fn read_vec(&mut self, address: usize, len: usize) -> io::Result<Vec<u8>> {
+        let mut buf = vec![0; len];
+        self.read_exact_at(address, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Reads a fixed-width string of `len` bytes at `address`, e.g. a name
+    /// field in a struct.
+    ///
+    /// Always reads exactly `len` bytes, decoding them as UTF-8 (lossily) and
+    /// trimming any trailing NUL padding. NULs before the end are kept.
+    pub fn read_string_fixed(&mut self, address: usize, len: usize) -> io::Result<String> {
+        let data = self.read_vec(address, len)?;
+        Ok(String::from_utf8_lossy(trim_nul_padding(&data)).into_owned())
+    }
+
+    /// Reads `len` bytes from the process' memory, starting at `address`,
+    /// handling unreadable memory according to `mode`.
+    ///
+    /// With `ReadMode::Strict`, this is the same as `Process::read_vec`.
+    pub fn read_vec_with(
+        &mut self,
+        address: usize,
+        len: usize,
+        mode: ReadMode,
+    ) -> io::Result<Vec<u8>> {
+        match mode {
+            ReadMode::Strict => self.read_vec(address, len),
+            ReadMode::ZeroFill => {
+                let mut buf = vec![0; len];
+                self.read_zero_filled(address, &mut buf);
+                Ok(buf)
+            }
+        }
+    }
+
+    /// Reads a single byte at `address` as a flag, returning `true` if it's
+    /// non-zero.
+    pub fn read_bool(&mut self, address: usize) -> io::Result<bool> {
+        let mut byte = [0];
+        self.read_exact_at(address, &mut byte)?;
+        Ok(byte[0] != 0)
+    }
+
+    /// Writes a flag as a single byte (`1` or `0`) at `address`. The rest of
+    /// the word containing it is left untouched.
+    pub fn write_bool(&mut self, address: usize, value: bool) -> io::Result<()> {
+        self.write_all_at(address, &[value as u8])
+    }
+
+    /// Writes all of `buf` into the process' memory, starting at `address`.
+    ///
+    /// Bytes past the end of `buf` in the last word are left untouched.
+    pub fn write_all_at(&mut self, address: usize, buf: &[u8]) -> io::Result<()> {
+        let mut writer = self.writer(address)?;
+        writer.write_all(buf)?;
+
+        let result = writer.flush();
+        if result.is_err() {
+            writer.discard();
+        }
+
+        result
+    }
+
+    /// Writes `s` into the process' memory at `address`, followed by a NUL
+    /// terminator, returning how many bytes were written (including the NUL).
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if `s` contains a NUL itself.
+    pub fn write_cstring(&mut self, address: usize, s: &str) -> io::Result<usize> {
+        if s.contains('\0') {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("String {s:?} contains a NUL byte"),
+            ));
+        }
+
+        let mut data = Vec::with_capacity(s.len() + 1);
+        data.extend_from_slice(s.as_bytes());
+        data.push(0);
+        self.write_all_at(address, &data)?;
+
+        Ok(data.len())
     }
 
     /// Resolves a chain of pointer offsets.
-    pub fn pointer_chain(&mut self, mut address: usize, offsets: Vec<isize>) -> io::Result<usize> {        
-        let mut reader = self.reader(address, POINTER_WIDTH)?.no_advance();
-
-        let mut address_bytes = [0; POINTER_WIDTH];
-        for offset in offsets.iter() {
-            reader.goto(address);
-            reader.read_exact(&mut address_bytes)?;
-            address = usize::from_le_bytes(address_bytes);
-
-            if *offset >= 0 {
-       			address += *offset as usize;
-       		} else {
-       			address -= offset.unsigned_abs();
-       		}
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if applying an offset would step
+    /// outside the address space.
+    pub fn pointer_chain(&mut self, address: usize, offsets: &[isize]) -> io::Result<usize> {
+        let pointer_width = self.target_pointer_width()?;
+        follow_chain(self, pointer_width, address, offsets)
+    }
+
+    /// Reads the pointer at `address`, returning where it points.
+    ///
+    /// Reads `Process::target_pointer_width()` bytes, so this is correct for
+    /// 32-bit processes too. The same as `pointer_chain(address, &[0])`.
+    pub fn deref(&mut self, address: usize) -> io::Result<usize> {
+        let pointer_width = self.target_pointer_width()?;
+        self.read_pointer(address, pointer_width)
+    }
+
+    /// Reads a `pointer_width`-byte pointer at `address`.
+    ///
+    /// Pointer chains are followed every frame by some tools, so this tries a
+    /// single `process_vm_readv` first, only falling back to
+    /// `Process::read_exact_at` (and its ptrace fallback and error reporting)
+    /// if that fails.
+    fn read_pointer(&mut self, address: usize, pointer_width: usize) -> io::Result<usize> {
+        let mut bytes = [0; 8];
+        let buf = &mut bytes[..pointer_width];
+
+        let read = Platform::read_memory(self.handle, address, buf).ok();
+        if read != Some(pointer_width) {
+            self.read_exact_at(address, buf)?;
         }
 
-        Ok(address)
+        Ok(usize::from_bytes(buf))
+    }
+
+    /// Resolves many chains of pointer offsets at once, returning one address
+    /// per chain (in order).
+    ///
+    /// The pointer width is only looked up once, which is cheaper than
+    /// calling `Process::pointer_chain` in a loop.
+    pub fn resolve_chains(&mut self, chains: &[(usize, &[isize])]) -> io::Result<Vec<usize>> {
+        let pointer_width = self.target_pointer_width()?;
+
+        chains
+            .iter()
+            .map(|(address, offsets)| follow_chain(self, pointer_width, *address, offsets))
+            .collect()
+    }
+
+    /// Returns the size of a page of memory, from `sysconf(_SC_PAGESIZE)`.
+    ///
+    /// This is usually 4096, but not always (e.g. on some aarch64 systems), so
+    /// use this to align page-sized operations instead of hardcoding it.
+    pub fn page_size(&self) -> usize {
+        sysconf(SysconfVar::PAGE_SIZE)
+            .ok()
+            .flatten()
+            .map_or(0x1000, |size| size as usize)
     }
 
     /// Returns the pid of the attached process.
@@ -276,11 +852,176 @@ impl Process {
         self.pid
     }
 
+    /// Returns the thread ids of the attached process (from `/proc/<pid>/task`), in ascending
+    /// order.
+    pub fn threads(&mut self) -> io::Result<Vec<Pid>> {
+        let dir = read_dir(format!("/proc/{}/task", self.pid))?;
+
+        let mut tids = Vec::new();
+        for entry in dir {
+            let entry = entry?;
+            if let Ok(tid) = entry.file_name().to_string_lossy().parse() {
+                tids.push(Pid::from_raw(tid));
+            }
+        }
+
+        tids.sort();
+        Ok(tids)
+    }
+
+    /// Returns the name of one of the process' threads (from
+    /// `/proc/<pid>/task/<tid>/comm`), e.g. to tell a render thread from the
+    /// main one.
+    pub fn thread_name(&self, tid: Pid) -> io::Result<String> {
+        let name = read_to_string(format!("/proc/{}/task/{tid}/comm", self.pid))?;
+        Ok(name.strip_suffix('\n').unwrap_or(&name).to_string())
+    }
+
+    /// Attaches to another thread of the process, leaving it stopped.
+    ///
+    /// `tid` must be one of `Process::threads`. Once attached, the thread can
+    /// be chosen with `Process::select_thread`. It's detached along with the
+    /// process.
+    pub fn attach_thread(&mut self, tid: Pid) -> io::Result<()> {
+        if tid == self.pid || self.attached_threads().any(|attached| attached == tid) {
+            return Ok(());
+        }
+
+        if !self.threads()?.contains(&tid) {
+            return Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("Process {} has no thread {tid}", self.pid),
+            ));
+        }
+
+        let handle = Platform::attach_stopped(tid)?;
+        self.attached_threads.push((tid, handle));
+
+        Ok(())
+    }
+
+    /// Returns the threads attached with `Process::attach_thread`.
+    pub fn attached_threads(&self) -> impl Iterator<Item = Pid> + '_ {
+        self.attached_threads.iter().map(|&(tid, _)| tid)
+    }
+
+    /// Chooses which thread register access and stepping act on.
+    ///
+    /// `tid` must be the process' pid (the main thread, which is the default)
+    /// or a thread attached with `Process::attach_thread`. You're responsible
+    /// for making sure the thread is stopped.
+    pub fn select_thread(&mut self, tid: Pid) -> io::Result<()> {
+        if tid != self.pid && !self.attached_threads().any(|attached| attached == tid) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Thread {tid} of process {} isn't attached", self.pid),
+            ));
+        }
+
+        self.current_thread = tid;
+        Ok(())
+    }
+
+    /// Returns the thread that register access and stepping act on.
+    pub fn current_thread(&self) -> Pid {
+        self.current_thread
+    }
+
+    /// Returns the backend's handle to `Process::current_thread`.
+    pub(crate) fn thread_handle(&self) -> Handle {
+        self.attached_threads
+            .iter()
+            .find(|&&(tid, _)| tid == self.current_thread)
+            .map_or(self.handle, |&(_, handle)| handle)
+    }
+
+    /// Returns whether the attached process still exists.
+    ///
+    /// Checks its state in `/proc/<pid>/stat`; a process that has exited
+    /// counts as dead, even if it hasn't been reaped yet (a zombie).
+    pub fn is_alive(&self) -> bool {
+        self.stat()
+            .is_ok_and(|stat| !matches!(stat.state, 'Z' | 'X'))
+    }
+
     /// Returns the full name of the attached process.
     pub fn name(&self) -> String {
         self.name.clone()
     }
 
+    /// Returns the command line of the attached process (from `/proc/<pid>/cmdline`), split into
+    /// arguments.
+    pub fn cmdline(&self) -> io::Result<Vec<String>> {
+        let data = read(format!("/proc/{}/cmdline", self.pid))?;
+        Ok(split_cmdline(&data))
+    }
+
+    /// Returns the environment variables of the attached process (from
+    /// `/proc/<pid>/environ`), as pairs of keys and values.
+    ///
+    /// This is the environment the process started with; changes it makes
+    /// later (e.g. with `setenv`) usually aren't reflected. Entries without
+    /// an `=` are returned with an empty value.
+    pub fn environ(&self) -> io::Result<Vec<(String, String)>> {
+        let data = read(format!("/proc/{}/environ", self.pid))?;
+
+        Ok(data
+            .split(|byte| *byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let entry = String::from_utf8_lossy(entry);
+                match entry.split_once('=') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => (entry.into_owned(), String::new()),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the path of the attached process' executable (from `/proc/<pid>/exe`).
+    pub fn exe_path(&self) -> io::Result<PathBuf> {
+        read_link(format!("/proc/{}/exe", self.pid)).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to read /proc/{}/exe: {e}", self.pid),
+            )
+        })
+    }
+
+    /// Returns the auxiliary vector of the attached process (from `/proc/<pid>/auxv`), as
+    /// pairs of `AT_*` keys and values.
+    ///
+    /// Includes entries like `AT_ENTRY` (the entry point) and `AT_SYSINFO_EHDR` (the vDSO),
+    /// which can be found in `nix::libc`. The terminating `AT_NULL` isn't included.
+    pub fn auxv(&self) -> io::Result<Vec<(u64, u64)>> {
+        const WORD: usize = std::mem::size_of::<usize>();
+
+        let data = read(format!("/proc/{}/auxv", self.pid))?;
+
+        Ok(data
+            .chunks_exact(WORD * 2)
+            .map(|entry| {
+                (
+                    usize::from_bytes(&entry[..WORD]) as u64,
+                    usize::from_bytes(&entry[WORD..]) as u64,
+                )
+            })
+            .take_while(|(key, _)| *key != 0)
+            .collect())
+    }
+
+    /// Adds `offset` to the base address, failing with `ErrorKind::InvalidInput`
+    /// instead of wrapping around.
+    fn checked_base_offset(&mut self, offset: usize) -> io::Result<usize> {
+        let base = self.base()?;
+        base.checked_add(offset).ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid address: 0x{base:x} + 0x{offset:x} is out of range"),
+            )
+        })
+    }
+
     /// Returns the base address of the attached process.
     pub fn base(&mut self) -> io::Result<usize> {
         self.get_base()?;
@@ -288,32 +1029,42 @@ impl Process {
     }
 
     /// Returns a `ProcessReader` for this process, good for `length` bytes, starting at `address`.
-    pub fn reader(&mut self, address: usize, length: usize) -> io::Result<ProcessReader> {
+    pub fn reader(&mut self, address: usize, length: usize) -> io::Result<ProcessReader<'_>> {
         self.get_base()?;
         Ok(ProcessReader::new(self, address, length))
     }
 
+    /// Returns a `ProcessReader` for this process, spanning the whole of
+    /// `region` (e.g. one from `Process::regions`).
+    pub fn reader_region(&mut self, region: &MemoryRegion) -> io::Result<ProcessReader<'_>> {
+        self.reader(region.start, region.len())
+    }
+
     /// Returns a `ProcessWriter` for this process, starting at `address`.
-    pub fn writer(&mut self, address: usize) -> io::Result<ProcessWriter> {
+    pub fn writer(&mut self, address: usize) -> io::Result<ProcessWriter<'_>> {
         self.get_base()?;
         Ok(ProcessWriter::new(self, address))
     }
 
     /// Returns a `ProcessReader` for this process, good for `length` bytes, starting at `offset`.
-    pub fn reader_offset(&mut self, offset: isize, length: usize) -> io::Result<ProcessReader> {
+    pub fn reader_offset(&mut self, offset: isize, length: usize) -> io::Result<ProcessReader<'_>> {
         self.get_base()?;
-        Ok(ProcessReader::offset(self, offset, length))
+        ProcessReader::offset(self, offset, length)
     }
 
     /// Returns a `ProcessWriter` for this process, starting at `offset`.
-    pub fn writer_offset(&mut self, offset: isize) -> io::Result<ProcessWriter> {
+    pub fn writer_offset(&mut self, offset: isize) -> io::Result<ProcessWriter<'_>> {
         self.get_base()?;
-        Ok(ProcessWriter::offset(self, offset))
+        ProcessWriter::offset(self, offset)
     }
 }
 
 impl Drop for Process {
     fn drop(&mut self) {
+        if self.detached {
+            return;
+        }
+
         if let Err(e) = self.detach_without_consuming() {
             panic!(
                 "Failed to detach from process {}: {e}",
@@ -322,3 +1073,65 @@ impl Drop for Process {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_status_means_vanished() {
+        let file = std::env::temp_dir().join(format!("memmod-empty-status-{}", std::process::id()));
+        File::create(&file).unwrap();
+
+        let e = get_process_status_name(file.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+
+        assert!(process_vanished(&e));
+    }
+
+    #[test]
+    fn split_cmdline_keeps_empty_args() {
+        assert_eq!(split_cmdline(b"sleep\x00100\x00"), ["sleep", "100"]);
+        assert_eq!(
+            split_cmdline(b"prog\x00\x00x\x00\x00"),
+            ["prog", "", "x", ""]
+        );
+        assert_eq!(split_cmdline(b"\x00"), [""]);
+        assert!(split_cmdline(b"").is_empty());
+    }
+
+    #[test]
+    fn split_cmdline_allows_missing_terminator() {
+        // Processes can overwrite their own command line, e.g. with
+        // `setproctitle`
+        assert_eq!(split_cmdline(b"worker: idle"), ["worker: idle"]);
+    }
+
+    #[test]
+    fn trim_nul_padding_only_trims_the_end() {
+        assert_eq!(trim_nul_padding(b"name\0\0\0\0"), b"name");
+        assert_eq!(trim_nul_padding(b"a\0b\0\0"), b"a\0b");
+        assert_eq!(trim_nul_padding(b"\0\0name"), b"\0\0name");
+        assert_eq!(trim_nul_padding(b"\0\0\0"), b"");
+        assert_eq!(trim_nul_padding(b""), b"");
+    }
+
+    #[test]
+    fn offset_address_applies_offsets() {
+        assert_eq!(offset_address(0x1000, 0x10).unwrap(), 0x1010);
+        assert_eq!(offset_address(0x1000, -0x10).unwrap(), 0xff0);
+        assert_eq!(offset_address(0x1000, -0x1000).unwrap(), 0);
+    }
+
+    #[test]
+    fn offset_address_rejects_overflow() {
+        let e = offset_address(0x1000, -0x1001).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+        let e = offset_address(usize::MAX, 1).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+        let e = offset_address(0, isize::MIN).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidInput);
+    }
+}
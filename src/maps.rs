@@ -0,0 +1,436 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    mem::size_of,
+    time::{Duration, Instant},
+};
+
+#[cfg(target_arch = "x86_64")]
+use nix::libc;
+
+use crate::{Process, ReadMode};
+
+/// How many bytes `Process::dump_region_with` reads at a time.
+const DUMP_CHUNK: usize = 0x1000;
+
+/// The width the kernel pads lines in `/proc/<pid>/maps` to before the path.
+const MAPS_PATH_COLUMN: usize = 25 + size_of::<usize>() * 6 - 1;
+
+/// How long `Process::is_readable` and `Process::is_writable` reuse the
+/// regions they read.
+const REGIONS_CACHE_TTL: Duration = Duration::from_millis(100);
+
+/// Memory protection flags, as found in `/proc/<pid>/maps` or passed to `mmap`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Perms {
+    pub read: bool,
+    pub write: bool,
+    pub exec: bool,
+}
+
+impl Perms {
+    /// Create a new set of permissions.
+    pub fn new(read: bool, write: bool, exec: bool) -> Self {
+        Self { read, write, exec }
+    }
+
+    /// Returns the equivalent `PROT_*` flags.
+    #[cfg(target_arch = "x86_64")]
+    pub(crate) fn prot(self) -> i32 {
+        let mut prot = libc::PROT_NONE;
+        if self.read {
+            prot |= libc::PROT_READ;
+        }
+        if self.write {
+            prot |= libc::PROT_WRITE;
+        }
+        if self.exec {
+            prot |= libc::PROT_EXEC;
+        }
+
+        prot
+    }
+}
+
+/// A single mapping in a process' memory (one line of `/proc/<pid>/maps`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// The first address of the mapping.
+    pub start: usize,
+    /// The address just past the end of the mapping.
+    pub end: usize,
+    pub perms: Perms,
+    /// Whether the mapping is shared (`s`) rather than private (`p`).
+    pub shared: bool,
+    /// The offset into the mapped file.
+    pub offset: usize,
+    /// The device of the mapped file, as `major:minor`.
+    pub dev: String,
+    /// The inode of the mapped file, or 0 if there isn't one.
+    pub inode: u64,
+    /// The mapped file, or a pseudo-path like `[heap]`. `None` for anonymous mappings.
+    pub path: Option<String>,
+}
+
+impl MemoryRegion {
+    /// Returns whether `address` is within the region.
+    pub fn contains(&self, address: usize) -> bool {
+        (self.start..self.end).contains(&address)
+    }
+
+    /// Returns the size of the region, in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns whether the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns whether the region belongs to the module `name`, which can be
+    /// the module's file name (e.g. `libc.so.6`), its full path, or a
+    /// pseudo-path like `[heap]`.
+    pub fn is_module(&self, name: &str) -> bool {
+        self.path
+            .as_deref()
+            .is_some_and(|path| path == name || path.rsplit('/').next() == Some(name))
+    }
+
+    /// Returns how many whole words of `pointer_width` bytes fit in the region
+    /// (see `Process::target_pointer_width`).
+    pub fn word_count(&self, pointer_width: usize) -> usize {
+        self.len() / pointer_width
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.splitn(6, ' ');
+
+        let (start, end) = parts.next()?.split_once('-')?;
+        let perms = parts.next()?.as_bytes();
+        if perms.len() != 4 {
+            return None;
+        }
+
+        let offset = parts.next()?;
+        let dev = parts.next()?;
+        let inode = parts.next()?;
+        let path = parts
+            .next()
+            .map(str::trim_start)
+            .filter(|path| !path.is_empty());
+
+        Some(Self {
+            start: usize::from_str_radix(start, 16).ok()?,
+            end: usize::from_str_radix(end, 16).ok()?,
+            perms: Perms {
+                read: perms[0] == b'r',
+                write: perms[1] == b'w',
+                exec: perms[2] == b'x',
+            },
+            shared: perms[3] == b's',
+            offset: usize::from_str_radix(offset, 16).ok()?,
+            dev: dev.to_string(),
+            inode: inode.parse().ok()?,
+            path: path.map(str::to_string),
+        })
+    }
+}
+
+/// Formats the region like a line of `/proc/<pid>/maps`, padding the path
+/// to the same column as the kernel.
+impl fmt::Display for MemoryRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = format!(
+            "{:08x}-{:08x} {}{}{}{} {:08x} {} {} ",
+            self.start,
+            self.end,
+            if self.perms.read { 'r' } else { '-' },
+            if self.perms.write { 'w' } else { '-' },
+            if self.perms.exec { 'x' } else { '-' },
+            if self.shared { 's' } else { 'p' },
+            self.offset,
+            self.dev,
+            self.inode,
+        );
+
+        match &self.path {
+            Some(path) => write!(f, "{line:<0$} {path}", MAPS_PATH_COLUMN),
+            None => f.write_str(&line),
+        }
+    }
+}
+
+impl Process {
+    /// Returns every mapping in the process' memory, as listed in `/proc/<pid>/maps`.
+    pub fn regions(&mut self) -> io::Result<Vec<MemoryRegion>> {
+        self.regions_iter()?.collect()
+    }
+
+    /// Returns an iterator over the mappings in the process' memory, parsing
+    /// `/proc/<pid>/maps` one line at a time.
+    ///
+    /// Useful if you only need the first few regions.
+    pub fn regions_iter(&mut self) -> io::Result<impl Iterator<Item = io::Result<MemoryRegion>>> {
+        let pid = self.pid;
+        let file = BufReader::new(File::open(format!("/proc/{pid}/maps"))?);
+
+        Ok(file.lines().map(move |line| {
+            MemoryRegion::parse(&line?).ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Bad format in /proc/{pid}/maps"),
+                )
+            })
+        }))
+    }
+
+    /// Returns the mapping containing `address`, or `None` if it isn't mapped.
+    pub fn which_region(&mut self, address: usize) -> io::Result<Option<MemoryRegion>> {
+        for region in self.regions_iter()? {
+            let region = region?;
+            if region.contains(address) {
+                return Ok(Some(region));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns whether `address` is in a readable mapping.
+    ///
+    /// A cheap pre-check for user-supplied addresses: the regions are cached
+    /// briefly, so checking many addresses in a row only reads
+    /// `/proc/<pid>/maps` once. Because of that, the answer may be slightly
+    /// out of date.
+    pub fn is_readable(&mut self, address: usize) -> io::Result<bool> {
+        Ok(self
+            .cached_region(address)?
+            .is_some_and(|region| region.perms.read))
+    }
+
+    /// Returns whether `address` is in a writable mapping.
+    ///
+    /// Uses the same cache as `Process::is_readable`. Note that writes go
+    /// through ptrace, which can also write to read-only mappings.
+    pub fn is_writable(&mut self, address: usize) -> io::Result<bool> {
+        Ok(self
+            .cached_region(address)?
+            .is_some_and(|region| region.perms.write))
+    }
+
+    /// Like `Process::which_region`, but reuses the regions read in the last
+    /// `REGIONS_CACHE_TTL`.
+    fn cached_region(&mut self, address: usize) -> io::Result<Option<&MemoryRegion>> {
+        let fresh = self
+            .regions_cache
+            .as_ref()
+            .is_some_and(|(read_at, _)| read_at.elapsed() < REGIONS_CACHE_TTL);
+        if !fresh {
+            self.regions_cache = Some((Instant::now(), self.regions()?));
+        }
+
+        let (_, regions) = self.regions_cache.as_ref().unwrap();
+        Ok(regions.iter().find(|region| region.contains(address)))
+    }
+
+    /// Returns the end of the run of contiguous mappings starting at
+    /// `address`, or `None` if it isn't mapped.
+    pub(crate) fn mapped_end(&mut self, address: usize) -> io::Result<Option<usize>> {
+        let mut end = None;
+        for region in self.regions_iter()? {
+            let region = region?;
+            match end {
+                None if region.contains(address) => end = Some(region.end),
+                Some(current) if region.start == current => end = Some(region.end),
+                Some(_) => break,
+                None => {}
+            }
+        }
+
+        Ok(end)
+    }
+
+    /// Describes `address` relative to the mapping it's in, e.g.
+    /// `libfoo.so+0x1234`.
+    ///
+    /// Addresses in a file are relative to where the file was first mapped,
+    /// and named after it. Other addresses are relative to the start of their
+    /// mapping, and are named after its pseudo-path (e.g. `[heap]+0x10`), or
+    /// `<anonymous>` if it doesn't have one. Unmapped addresses are just
+    /// `<unmapped>`.
+    pub fn describe_address(&mut self, address: usize) -> io::Result<String> {
+        let regions = self.regions()?;
+        let Some(region) = regions.iter().find(|region| region.contains(address)) else {
+            return Ok("<unmapped>".to_string());
+        };
+
+        let (name, base) = match &region.path {
+            Some(path) if path.starts_with('/') => {
+                let base = regions
+                    .iter()
+                    .filter(|other| other.path == region.path)
+                    .map(|other| other.start)
+                    .min()
+                    .unwrap_or(region.start);
+                let name = path.rsplit('/').next().unwrap_or(path);
+
+                (name, base)
+            }
+            Some(path) => (path.as_str(), region.start),
+            None => ("<anonymous>", region.start),
+        };
+
+        Ok(format!("{name}+0x{:x}", address - base))
+    }
+
+    /// Returns the address a module was first mapped at, or `None` if it
+    /// isn't mapped.
+    ///
+    /// `name` can be the module's file name (e.g. `libc.so.6`), its full path,
+    /// or a pseudo-path like `[heap]`.
+    pub fn module_base(&mut self, name: &str) -> io::Result<Option<usize>> {
+        Ok(self.module_first_region(name)?.map(|region| region.start))
+    }
+
+    /// Returns the lowest mapping of the module `name` (matched like in
+    /// `Process::module_base`), or `None` if it isn't mapped.
+    pub(crate) fn module_first_region(&mut self, name: &str) -> io::Result<Option<MemoryRegion>> {
+        let mut first: Option<MemoryRegion> = None;
+        for region in self.regions_iter()? {
+            let region = region?;
+            if !region.is_module(name) {
+                continue;
+            }
+
+            match &first {
+                Some(first) if first.start <= region.start => {}
+                _ => first = Some(region),
+            }
+        }
+
+        Ok(first)
+    }
+
+    /// Returns the range of addresses a module is mapped in, as `(start, end)`,
+    /// from the start of its first mapping to the end of its last.
+    ///
+    /// `name` is matched like in `Process::module_base`. Fails with
+    /// `ErrorKind::NotFound` if the module isn't mapped.
+    pub fn module_range(&mut self, name: &str) -> io::Result<(usize, usize)> {
+        let mut range: Option<(usize, usize)> = None;
+        for region in self.regions_iter()? {
+            let region = region?;
+            if region.is_module(name) {
+                range = Some(match range {
+                    Some((start, end)) => (start.min(region.start), end.max(region.end)),
+                    None => (region.start, region.end),
+                });
+            }
+        }
+
+        range.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find module `{name}`"),
+            )
+        })
+    }
+
+    /// Resolves a string like `game+0x1a0` or `libc.so.6-0x10` to an
+    /// absolute address, the inverse of `Process::describe_address`.
+    ///
+    /// The module is found with `Process::module_base`. The offset is in hex,
+    /// with or without a `0x` prefix.
+    pub fn resolve_module_offset(&mut self, spec: &str) -> io::Result<usize> {
+        let invalid = || {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid module offset `{spec}`"),
+            )
+        };
+
+        let split = spec.rfind(['+', '-']).ok_or_else(invalid)?;
+        let (module, offset) = spec.split_at(split);
+        let (sign, offset) = offset.split_at(1);
+        let offset = offset.strip_prefix("0x").unwrap_or(offset);
+        let offset = usize::from_str_radix(offset, 16).map_err(|_| invalid())?;
+
+        let base = self.module_base(module)?.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                format!("Failed to find module `{module}`"),
+            )
+        })?;
+
+        if sign == "+" {
+            base.checked_add(offset)
+        } else {
+            base.checked_sub(offset)
+        }
+        .ok_or_else(invalid)
+    }
+
+    /// Writes the contents of `region` into `out`, returning the number of
+    /// bytes written.
+    ///
+    /// Reads a page at a time. If a page can't be read (e.g. a guard page),
+    /// the dump stops there, so the returned count may be less than the size
+    /// of the region. Errors from `out` are returned as normal.
+    pub fn dump_region<W: Write>(&mut self, region: &MemoryRegion, out: &mut W) -> io::Result<u64> {
+        self.dump_region_with(region, out, ReadMode::Strict)
+    }
+
+    /// Like `Process::dump_region`, but with `ReadMode::ZeroFill`, pages that
+    /// can't be read are written as zeros instead of ending the dump, so the
+    /// whole region is always written.
+    pub fn dump_region_with<W: Write>(
+        &mut self,
+        region: &MemoryRegion,
+        out: &mut W,
+        mode: ReadMode,
+    ) -> io::Result<u64> {
+        let mut buf = [0; DUMP_CHUNK];
+        let mut written = 0;
+
+        let mut address = region.start;
+        while address < region.end {
+            let len = DUMP_CHUNK.min(region.end - address);
+            if self.read_exact_at(address, &mut buf[..len]).is_err() {
+                match mode {
+                    ReadMode::Strict => break,
+                    ReadMode::ZeroFill => self.read_zero_filled(address, &mut buf[..len]),
+                }
+            }
+
+            out.write_all(&buf[..len])?;
+            written += len as u64;
+            address += len;
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(path: &str) -> MemoryRegion {
+        MemoryRegion::parse(&format!("1000-2000 r--p 00000000 00:00 0 {path}")).unwrap()
+    }
+
+    #[test]
+    fn modules_match_by_name_or_path() {
+        let libc = region("/usr/lib/libc.so.6");
+        assert!(libc.is_module("libc.so.6"));
+        assert!(libc.is_module("/usr/lib/libc.so.6"));
+        assert!(!libc.is_module("libc.so"));
+        assert!(!libc.is_module("lib/libc.so.6"));
+
+        assert!(region("[heap]").is_module("[heap]"));
+        let anonymous = MemoryRegion::parse("1000-2000 rw-p 00000000 00:00 0").unwrap();
+        assert!(!anonymous.is_module(""));
+    }
+}
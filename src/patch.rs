@@ -0,0 +1,110 @@
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+};
+
+use crate::Process;
+
+/// The x86 `nop` instruction.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const NOP: u8 = 0x90;
+
+/// A patch applied to a process' memory.
+///
+/// Remembers the bytes it replaced, so it can be undone with
+/// `Patch::revert`. Created via `Process::patch`. Dropping a patch leaves it
+/// applied; to revert it automatically, use `Patch::revert_on_drop`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    address: usize,
+    original: Vec<u8>,
+}
+
+impl Patch {
+    /// Returns the address the patch was applied at.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Returns the bytes the patch replaced.
+    pub fn original(&self) -> &[u8] {
+        &self.original
+    }
+
+    /// Undoes the patch, restoring the bytes it replaced.
+    pub fn revert(self, proc: &mut Process) -> io::Result<()> {
+        proc.write_all_at(self.address, &self.original)
+    }
+
+    /// Returns a `PatchGuard` that reverts the patch when dropped.
+    pub fn revert_on_drop(self, proc: &mut Process) -> PatchGuard<'_> {
+        PatchGuard { proc, patch: self }
+    }
+}
+
+/// A guard that reverts a patch when it's dropped.
+///
+/// Created via `Patch::revert_on_drop`. Can be dereferenced to the underlying
+/// `Process`.
+#[derive(Debug)]
+pub struct PatchGuard<'a> {
+    proc: &'a mut Process,
+
+    patch: Patch,
+}
+
+impl<'a> Deref for PatchGuard<'a> {
+    type Target = Process;
+
+    fn deref(&self) -> &Self::Target {
+        self.proc
+    }
+}
+
+impl<'a> DerefMut for PatchGuard<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.proc
+    }
+}
+
+impl<'a> Drop for PatchGuard<'a> {
+    fn drop(&mut self) {
+        if let Err(e) = self
+            .proc
+            .write_all_at(self.patch.address, &self.patch.original)
+        {
+            if !std::thread::panicking() {
+                panic!(
+                    "Failed to revert patch at 0x{:x} in process {} after patch guard was dropped: {e}",
+                    self.patch.address, self.pid
+                );
+            }
+        }
+    }
+}
+
+impl Process {
+    /// Writes `bytes` at `address`, returning a `Patch` that can undo it.
+    ///
+    /// Writes go through ptrace, so this works on read-only (e.g. code)
+    /// mappings too. Bytes around the patched range are left untouched.
+    pub fn patch(&mut self, address: usize, bytes: &[u8]) -> io::Result<Patch> {
+        let original = self.read_vec(address, bytes.len())?;
+        self.write_all_at(address, bytes)?;
+
+        Ok(Patch { address, original })
+    }
+
+    /// Overwrites `len` bytes at `address` with x86 `nop` instructions,
+    /// returning the bytes that were there before so the patch can be undone.
+    ///
+    /// Writes go through ptrace, so this works on read-only (e.g. code)
+    /// mappings too. Bytes around the patched range are left untouched.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn nop(&mut self, address: usize, len: usize) -> io::Result<Vec<u8>> {
+        let original = self.read_vec(address, len)?;
+        self.write_all_at(address, &vec![NOP; len])?;
+
+        Ok(original)
+    }
+}
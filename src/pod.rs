@@ -0,0 +1,41 @@
+use std::mem::size_of;
+
+/// "Plain old data": types that have no padding, and are valid for any bit
+/// pattern. Values of these types can be copied straight to and from a
+/// process' memory.
+///
+/// # Safety
+///
+/// Only implement this for types that are `#[repr(C)]` (or primitive), have
+/// no padding bytes, and for which every possible bit pattern is valid.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Returns the bytes making up `value`.
+pub(crate) fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }
+}
+
+/// Returns the bytes making up every value in `values`.
+pub(crate) fn bytes_of_slice<T: Pod>(values: &[T]) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(values.as_ptr() as *const u8, std::mem::size_of_val(values))
+    }
+}
+
+/// Reinterprets the first `size_of::<T>()` bytes of `bytes` as a `T`.
+///
+/// Panics if `bytes` is too short.
+pub(crate) fn from_bytes<T: Pod>(bytes: &[u8]) -> T {
+    assert!(bytes.len() >= size_of::<T>());
+    unsafe { (bytes.as_ptr() as *const T).read_unaligned() }
+}
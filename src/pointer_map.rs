@@ -0,0 +1,146 @@
+use std::{io, ops::Range};
+
+use crate::{Address, Process, RegionFilter};
+
+/// How many bytes `Process::pointer_map` reads at a time.
+const POINTER_MAP_CHUNK: usize = 0x10000;
+
+/// Every pointer in a process' writable memory, for finding pointer paths to
+/// an address (a "pointer scan").
+///
+/// Build one with `Process::pointer_map`, then search it with
+/// `PointerMap::find_paths`. The map is a copy; it doesn't change along with
+/// the process.
+#[derive(Debug, Clone)]
+pub struct PointerMap {
+    /// `(value, address)` pairs, sorted by value.
+    pointers: Vec<(usize, usize)>,
+    /// The process' base address.
+    base: usize,
+    /// The regions of the main executable, where paths can start.
+    statics: Vec<Range<usize>>,
+}
+
+impl PointerMap {
+    /// Returns how many pointers were found.
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    /// Returns whether no pointers were found.
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty()
+    }
+
+    /// Finds chains of pointers that lead to `target`, following at most
+    /// `max_depth` pointers, each with an offset of at most `max_offset`.
+    ///
+    /// The first element of each path is the offset of the starting pointer
+    /// from the process' base address (which is in the main executable, so it
+    /// stays the same between runs). The rest are the offsets to follow, so
+    /// `proc.pointer_chain(proc.base()? + path[0], &path[1..])` gets you back
+    /// to `target`. Shorter paths come first.
+    pub fn find_paths(
+        &self,
+        target: usize,
+        max_depth: usize,
+        max_offset: usize,
+    ) -> Vec<Vec<isize>> {
+        let mut paths = Vec::new();
+        let mut level = vec![(target, Vec::new())];
+
+        for _ in 0..max_depth {
+            let mut next = Vec::new();
+            for (target, offsets) in level {
+                let low = target.saturating_sub(max_offset);
+                let start = self.pointers.partition_point(|&(value, _)| value < low);
+
+                for &(value, address) in self.pointers[start..]
+                    .iter()
+                    .take_while(|&&(value, _)| value <= target)
+                {
+                    let mut offsets = offsets.clone();
+                    offsets.insert(0, (target - value) as isize);
+
+                    if self.statics.iter().any(|range| range.contains(&address)) {
+                        offsets.insert(0, address.wrapping_sub(self.base) as isize);
+                        paths.push(offsets);
+                    } else {
+                        next.push((address, offsets));
+                    }
+                }
+            }
+
+            level = next;
+        }
+
+        paths
+    }
+}
+
+impl Process {
+    /// Builds a map of every pointer-aligned word in the writable regions of
+    /// the process' memory whose value points into a mapped region.
+    ///
+    /// This reads a lot of memory, and the map can get big. Parts of a region
+    /// that can't be read are skipped.
+    pub fn pointer_map(&mut self) -> io::Result<PointerMap> {
+        let pointer_width = self.target_pointer_width()?;
+        let base = self.base()?;
+        let regions = self.regions()?;
+
+        let mut mapped: Vec<_> = regions
+            .iter()
+            .map(|region| region.start..region.end)
+            .collect();
+        mapped.sort_by_key(|range| range.start);
+        let is_mapped = |value: usize| {
+            let i = mapped.partition_point(|range| range.end <= value);
+            mapped.get(i).is_some_and(|range| range.contains(&value))
+        };
+
+        let main = regions
+            .iter()
+            .find(|region| region.contains(base))
+            .and_then(|region| region.path.clone());
+        let statics = regions
+            .iter()
+            .filter(|region| main.is_some() && region.path == main)
+            .map(|region| region.start..region.end)
+            .collect();
+
+        let filter = RegionFilter::new().writable();
+        let mut pointers = Vec::new();
+        let mut buf = vec![0; POINTER_MAP_CHUNK];
+
+        for region in &regions {
+            let Some(bounds) = filter.bounds(region) else {
+                continue;
+            };
+
+            let mut address = bounds.start.next_multiple_of(pointer_width);
+            while address + pointer_width <= bounds.end {
+                let len =
+                    POINTER_MAP_CHUNK.min(bounds.end - address) / pointer_width * pointer_width;
+                if self.read_memory(address, &mut buf[..len]).is_ok() {
+                    for (i, word) in buf[..len].chunks_exact(pointer_width).enumerate() {
+                        let value = usize::from_bytes(word);
+                        if is_mapped(value) {
+                            pointers.push((value, address + i * pointer_width));
+                        }
+                    }
+                }
+
+                address += len;
+            }
+        }
+
+        pointers.sort_unstable();
+
+        Ok(PointerMap {
+            pointers,
+            base,
+            statics,
+        })
+    }
+}
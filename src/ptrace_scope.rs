@@ -0,0 +1,82 @@
+use std::{
+    fmt,
+    fs::read_to_string,
+    io::{self, ErrorKind},
+};
+
+use crate::Pid;
+
+/// Yama's restrictions on ptrace, from `/proc/sys/kernel/yama/ptrace_scope`.
+///
+/// See `ptrace_permitted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PtraceScope {
+    /// Scope 0: any process running as the same user can be attached to.
+    Unrestricted,
+    /// Scope 1: only descendants can be attached to, unless the target allows
+    /// it (with `PR_SET_PTRACER`) or the tracer has `CAP_SYS_PTRACE`.
+    Restricted,
+    /// Scope 2: only tracers with `CAP_SYS_PTRACE` (usually root) can attach.
+    AdminOnly,
+    /// Scope 3: nothing can attach, until the next reboot.
+    NoAttach,
+}
+
+impl fmt::Display for PtraceScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (scope, name) = match self {
+            Self::Unrestricted => (0, "unrestricted"),
+            Self::Restricted => (1, "restricted"),
+            Self::AdminOnly => (2, "admin-only"),
+            Self::NoAttach => (3, "no attach"),
+        };
+
+        write!(f, "{scope} ({name})")
+    }
+}
+
+/// Reads which processes Yama allows ptrace to attach to.
+///
+/// If Yama isn't enabled, there are no extra restrictions, so this returns
+/// `PtraceScope::Unrestricted`. Attaching still requires the usual
+/// permissions (the same user, or `CAP_SYS_PTRACE`).
+pub fn ptrace_permitted() -> io::Result<PtraceScope> {
+    let scope = match read_to_string("/proc/sys/kernel/yama/ptrace_scope") {
+        Ok(scope) => scope,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(PtraceScope::Unrestricted),
+        Err(e) => return Err(e),
+    };
+
+    match scope.trim() {
+        "0" => Ok(PtraceScope::Unrestricted),
+        "1" => Ok(PtraceScope::Restricted),
+        "2" => Ok(PtraceScope::AdminOnly),
+        "3" => Ok(PtraceScope::NoAttach),
+        other => Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Unknown ptrace scope `{other}`"),
+        )),
+    }
+}
+
+/// Turns an `EPERM` from attaching into an error explaining why, including
+/// the ptrace scope. Other errors are returned as-is.
+pub(crate) fn attach_error(pid: Pid, e: io::Error) -> io::Error {
+    if e.raw_os_error() != Some(nix::libc::EPERM) {
+        return e;
+    }
+
+    let scope = match ptrace_permitted() {
+        Ok(scope) => scope.to_string(),
+        Err(_) => "unknown".to_string(),
+    };
+
+    io::Error::new(
+        ErrorKind::PermissionDenied,
+        format!(
+            "Not permitted to attach to process {pid} with ptrace scope {scope}; \
+             check it isn't already being traced, and try running as root or \
+             lowering /proc/sys/kernel/yama/ptrace_scope"
+        ),
+    )
+}
@@ -1,17 +1,45 @@
 use std::{
-    io::{self, Read},
+    io::{self, BufRead, BufReader, ErrorKind, Read},
+    iter,
+    mem::size_of,
     ops::{Deref, DerefMut},
 };
 
-use crate::Process;
+use crate::{
+    offset_address,
+    pod::{from_bytes, Pod},
+    Process,
+};
+
+/// How many bytes `ProcessReader::find` reads at a time.
+const FIND_CHUNK: usize = 0x1000;
+
+/// How bulk reads (like `Process::read_vec_with`) handle memory that can't be
+/// read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadMode {
+    /// Fail at the first byte that can't be read.
+    #[default]
+    Strict,
+    /// Substitute zeros for every page that can't be read (e.g. guard pages
+    /// and unbacked holes), and carry on.
+    ZeroFill,
+}
 
 /// A reader for a process.
 ///
-/// Reads `length` bytes at a time. Every read
-/// will return the same slice of memory. Sequential
+/// Reads up to `length` bytes of memory. Sequential
 /// reads advance through the process' memory by
-/// default. To disable this behavior, use
-/// `ProcessReader::no_advance`.
+/// default, and once `length` bytes have been read,
+/// reads return 0 (EOF). To disable this behavior,
+/// use `ProcessReader::no_advance`; every read will
+/// then return the same slice of memory (up to
+/// `length` bytes).
+///
+/// A read that runs off the end of mapped memory
+/// stops at the boundary, returning fewer bytes. A
+/// read that starts in unmapped memory fails with
+/// `ErrorKind::InvalidInput`, naming the address.
 ///
 /// Can be dereferenced to the underlying `Process`.
 #[derive(Debug)]
@@ -35,19 +63,17 @@ impl<'a> ProcessReader<'a> {
     }
 
     /// Create a new process reader at `offset`.
-    pub fn offset(proc: &'a mut Process, offset: isize, length: usize) -> Self {
-        let base = proc.base.unwrap();
-        let address = if offset >= 0 {
-            base + offset as usize
-        } else {
-            base - offset as usize
-        };
-        Self {
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if the resulting address is out of
+    /// range.
+    pub fn offset(proc: &'a mut Process, offset: isize, length: usize) -> io::Result<Self> {
+        let address = offset_address(proc.base()?, offset)?;
+        Ok(Self {
             proc,
             address,
             length,
             advance: true,
-        }
+        })
     }
 
     /// Disables advancing through memory.
@@ -62,35 +88,153 @@ impl<'a> ProcessReader<'a> {
         self
     }
 
+    /// Returns the address the next read will start at.
+    pub fn position(&self) -> usize {
+        self.address
+    }
+
+    /// Returns how many bytes are left to read.
+    pub fn remaining(&self) -> usize {
+        self.length
+    }
+
+    /// Returns an iterator over the lines of text in the reader's memory.
+    ///
+    /// The reader should be advancing, or this will never end.
+    pub fn lines(self) -> impl Iterator<Item = io::Result<String>> + 'a {
+        BufReader::new(self).lines()
+    }
+
+    /// Returns an iterator that reads one `T` per item, until fewer than
+    /// `size_of::<T>()` bytes are left in the window.
+    ///
+    /// The reader should be advancing, or this will never end. Iteration
+    /// stops after the first error.
+    pub fn iter_values<T: Pod>(&mut self) -> impl Iterator<Item = io::Result<T>> + '_ {
+        let mut buf = vec![0; size_of::<T>()];
+        let mut failed = false;
+
+        // Reborrow as a reader with a shorter lifetime, so the iterator only
+        // captures the `'_` one
+        let mut reader = ProcessReader {
+            proc: &mut *self.proc,
+            address: self.address,
+            length: self.length,
+            advance: self.advance,
+        };
+        let (address, length) = (&mut self.address, &mut self.length);
+
+        iter::from_fn(move || {
+            if failed || buf.is_empty() || reader.length < buf.len() {
+                return None;
+            }
+
+            let result = reader.read_exact(&mut buf);
+            (*address, *length) = (reader.address, reader.length);
+            if let Err(e) = result {
+                failed = true;
+                return Some(Err(e));
+            }
+
+            Some(Ok(from_bytes(&buf)))
+        })
+    }
+
+    /// Searches the rest of the reader's window (`remaining()` bytes from
+    /// `position()`) for `needle`, returning the address of the first match.
+    ///
+    /// Doesn't move the reader.
+    pub fn find(&mut self, needle: &[u8]) -> io::Result<Option<usize>> {
+        if needle.is_empty() {
+            return Ok(Some(self.address));
+        }
+
+        let end = self.address + self.length;
+        let mut buf = vec![0; FIND_CHUNK.max(needle.len())];
+        let mut address = self.address;
+        while address + needle.len() <= end {
+            let len = buf.len().min(end - address);
+            self.proc.read_memory(address, &mut buf[..len])?;
+
+            if let Some(i) = buf[..len]
+                .windows(needle.len())
+                .position(|window| window == needle)
+            {
+                return Ok(Some(address + i));
+            }
+
+            if address + len == end {
+                break;
+            }
+
+            // Overlap chunks, in case a match straddles two of them
+            address += len - (needle.len() - 1);
+        }
+
+        Ok(None)
+    }
+
     /// Jumps to an address in memory.
     pub fn goto(&mut self, address: usize) {
         self.address = address;
     }
-    
+
     /// Jumps to an offset in memory.
-    pub fn goto_offset(&mut self, offset: isize) {
-        self.address = if offset >= 0 {
-            self.proc.base().unwrap() + offset as usize
-        } else {
-            self.proc.base().unwrap() - offset as usize
-        };
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if the resulting address is out of
+    /// range, leaving the position unchanged.
+    pub fn goto_offset(&mut self, offset: isize) -> io::Result<()> {
+        self.address = offset_address(self.proc.base()?, offset)?;
+        Ok(())
     }
 }
 
-impl<'a> Read for ProcessReader<'a> {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let length = buf.len().min(self.length);
+impl Process {
+    /// Reads `buf.len()` bytes at `address`, zeroing the parts of any pages
+    /// that can't be read.
+    pub(crate) fn read_zero_filled(&mut self, address: usize, buf: &mut [u8]) {
+        if self.read_memory(address, buf).is_ok() {
+            return;
+        }
+
+        let page_size = self.page_size();
+        let mut done = 0;
+        while done < buf.len() {
+            let start = address + done;
+            let len = (page_size - start % page_size).min(buf.len() - done);
+
+            let chunk = &mut buf[done..done + len];
+            if self.read_memory(start, chunk).is_err() {
+                chunk.fill(0);
+            }
 
-        for i in (0..length).step_by(8) {
-            let word = self.proc.read_word(self.address + i)?;
+            done += len;
+        }
+    }
+}
 
-            for j in 0..8 {
-                buf[i + j] = (word >> (j * 8)) as u8;
+impl<'a> Read for ProcessReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut length = buf.len().min(self.length);
+        if let Err(e) = self.proc.read_memory(self.address, &mut buf[..length]) {
+            // Stop at the end of the mapping, if that's what went wrong
+            let end = self.proc.mapped_end(self.address)?.ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Address 0x{:x} isn't mapped", self.address),
+                )
+            })?;
+            if end - self.address >= length {
+                return Err(e);
             }
+
+            length = end - self.address;
+            self.proc.read_memory(self.address, &mut buf[..length])?;
         }
 
         if self.advance {
             self.address += length;
+            self.length -= length;
         }
 
         Ok(length)
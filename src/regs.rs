@@ -0,0 +1,352 @@
+use std::io;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use std::{fmt, io::ErrorKind};
+
+#[cfg(target_arch = "x86_64")]
+pub use nix::libc::user_fpregs_struct;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub use nix::libc::user_regs_struct;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use crate::backend::{Backend, Platform};
+use crate::Process;
+
+impl Process {
+    /// Reads the process' general-purpose registers.
+    ///
+    /// Reads from the thread chosen with `Process::select_thread` (the main
+    /// thread by default).
+    ///
+    /// The process must be stopped for this to work, so this stops it first
+    /// if it isn't already. Only available on x86_64 and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn get_regs(&mut self) -> io::Result<user_regs_struct> {
+        self.stop()?;
+
+        Platform::get_regs(self.thread_handle())
+    }
+
+    /// Overwrites the process' general-purpose registers.
+    ///
+    /// Writes to the thread chosen with `Process::select_thread` (the main
+    /// thread by default).
+    ///
+    /// Unlike `Process::get_regs`, this doesn't stop the process itself; it
+    /// fails if the process isn't already stopped. Only available on x86_64
+    /// and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn set_regs(&mut self, regs: user_regs_struct) -> io::Result<()> {
+        if !self.stopped {
+            return Err(io::Error::other(format!(
+                "Process {} must be stopped to set its registers",
+                self.pid
+            )));
+        }
+
+        Platform::set_regs(self.thread_handle(), regs)
+    }
+
+    /// Reads the process' floating-point and SSE registers: the x87 stack,
+    /// `mxcsr`, and `xmm0` through `xmm15`.
+    ///
+    /// `user_fpregs_struct` is libc's copy of the kernel's
+    /// `struct user_i387_struct` (see `<sys/user.h>`), which is laid out like
+    /// the `fxsave` area. Reads from the thread chosen with
+    /// `Process::select_thread`, stopping the process first if it isn't
+    /// already. Only available on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    pub fn get_fpregs(&mut self) -> io::Result<user_fpregs_struct> {
+        self.stop()?;
+
+        Platform::get_fpregs(self.thread_handle())
+    }
+
+    /// Overwrites the process' floating-point and SSE registers (see
+    /// `Process::get_fpregs`).
+    ///
+    /// Like `Process::set_regs`, this fails if the process isn't already
+    /// stopped. Only available on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    pub fn set_fpregs(&mut self, regs: user_fpregs_struct) -> io::Result<()> {
+        if !self.stopped {
+            return Err(io::Error::other(format!(
+                "Process {} must be stopped to set its registers",
+                self.pid
+            )));
+        }
+
+        Platform::set_fpregs(self.thread_handle(), regs)
+    }
+
+    /// Reads the process' general-purpose registers, wrapped so the common
+    /// ones can be used without caring about the architecture.
+    ///
+    /// Like `Process::get_regs`, this stops the process first. Only available
+    /// on x86_64 and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn registers(&mut self) -> io::Result<Registers> {
+        self.get_regs().map(Registers)
+    }
+
+    /// Overwrites the process' general-purpose registers (see
+    /// `Process::registers`).
+    ///
+    /// Like `Process::set_regs`, this fails if the process isn't already
+    /// stopped. Only available on x86_64 and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn set_registers(&mut self, regs: Registers) -> io::Result<()> {
+        self.set_regs(regs.0)
+    }
+
+    /// Reads the instruction pointer (`rip` on x86_64, `pc` on aarch64) of the
+    /// process.
+    ///
+    /// Like `Process::get_regs`, this stops the process first. Only available
+    /// on x86_64 and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn instruction_pointer(&mut self) -> io::Result<usize> {
+        Ok(self.registers()?.instruction_pointer())
+    }
+
+    /// Sets the instruction pointer (`rip` on x86_64, `pc` on aarch64) of the
+    /// process, leaving the other registers alone.
+    ///
+    /// Only available on x86_64 and aarch64 for now.
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub fn set_instruction_pointer(&mut self, ip: usize) -> io::Result<()> {
+        let mut regs = self.registers()?;
+        regs.set_instruction_pointer(ip);
+        self.set_registers(regs)
+    }
+}
+
+/// How many function arguments `Registers::argument` can access.
+#[cfg(target_arch = "x86_64")]
+pub const ARGUMENT_REGISTERS: usize = 6;
+/// How many function arguments `Registers::argument` can access.
+#[cfg(target_arch = "aarch64")]
+pub const ARGUMENT_REGISTERS: usize = 8;
+
+/// A thread's general-purpose registers, with accessors for the ones every
+/// architecture has, so callers don't need `#[cfg]` for them.
+///
+/// Created via `Process::registers`. Anything architecture-specific is still
+/// available through `Registers::raw` and `Registers::raw_mut`.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[derive(Clone, Copy)]
+pub struct Registers(user_regs_struct);
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+impl Registers {
+    /// Returns the instruction pointer (`rip` on x86_64, `pc` on aarch64).
+    pub fn instruction_pointer(&self) -> usize {
+        #[cfg(target_arch = "x86_64")]
+        return self.0.rip as usize;
+
+        #[cfg(target_arch = "aarch64")]
+        return self.0.pc as usize;
+    }
+
+    /// Sets the instruction pointer (`rip` on x86_64, `pc` on aarch64).
+    pub fn set_instruction_pointer(&mut self, ip: usize) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rip = ip as u64;
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            self.0.pc = ip as u64;
+        }
+    }
+
+    /// Returns the stack pointer (`rsp` on x86_64, `sp` on aarch64).
+    pub fn stack_pointer(&self) -> usize {
+        #[cfg(target_arch = "x86_64")]
+        return self.0.rsp as usize;
+
+        #[cfg(target_arch = "aarch64")]
+        return self.0.sp as usize;
+    }
+
+    /// Sets the stack pointer (`rsp` on x86_64, `sp` on aarch64).
+    pub fn set_stack_pointer(&mut self, sp: usize) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rsp = sp as u64;
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            self.0.sp = sp as u64;
+        }
+    }
+
+    /// Returns the register holding function and syscall return values
+    /// (`rax` on x86_64, `x0` on aarch64).
+    pub fn return_value(&self) -> usize {
+        #[cfg(target_arch = "x86_64")]
+        return self.0.rax as usize;
+
+        #[cfg(target_arch = "aarch64")]
+        return self.0.regs[0] as usize;
+    }
+
+    /// Sets the register holding function and syscall return values (`rax` on
+    /// x86_64, `x0` on aarch64).
+    pub fn set_return_value(&mut self, value: usize) {
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.0.rax = value as u64;
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            self.0.regs[0] = value as u64;
+        }
+    }
+
+    /// Returns the `n`th (from 0) integer argument of a function call, going by
+    /// the platform's C calling convention: `rdi`, `rsi`, `rdx`, `rcx`, `r8`
+    /// and `r9` on x86_64, `x0` through `x7` on aarch64.
+    ///
+    /// Returns `None` if `n` isn't below `ARGUMENT_REGISTERS` (the rest are
+    /// passed on the stack).
+    pub fn argument(&self, n: usize) -> Option<usize> {
+        self.argument_register(n).map(|register| register as usize)
+    }
+
+    /// Sets the `n`th (from 0) integer argument of a function call (see
+    /// `Registers::argument`).
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if `n` isn't below
+    /// `ARGUMENT_REGISTERS`.
+    pub fn set_argument(&mut self, n: usize, value: usize) -> io::Result<()> {
+        let register = self.argument_register_mut(n).ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Argument {n} isn't passed in a register"),
+            )
+        })?;
+        *register = value as u64;
+
+        Ok(())
+    }
+
+    /// Returns the raw, architecture-specific registers.
+    pub fn raw(&self) -> &user_regs_struct {
+        &self.0
+    }
+
+    /// Returns the raw, architecture-specific registers, for changing them.
+    pub fn raw_mut(&mut self) -> &mut user_regs_struct {
+        &mut self.0
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn argument_register(&self, n: usize) -> Option<u64> {
+        let regs = &self.0;
+        [regs.rdi, regs.rsi, regs.rdx, regs.rcx, regs.r8, regs.r9]
+            .get(n)
+            .copied()
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn argument_register(&self, n: usize) -> Option<u64> {
+        self.0.regs[..ARGUMENT_REGISTERS].get(n).copied()
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn argument_register_mut(&mut self, n: usize) -> Option<&mut u64> {
+        let regs = &mut self.0;
+        [
+            &mut regs.rdi,
+            &mut regs.rsi,
+            &mut regs.rdx,
+            &mut regs.rcx,
+            &mut regs.r8,
+            &mut regs.r9,
+        ]
+        .into_iter()
+        .nth(n)
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn argument_register_mut(&mut self, n: usize) -> Option<&mut u64> {
+        self.0.regs[..ARGUMENT_REGISTERS].get_mut(n)
+    }
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+impl From<user_regs_struct> for Registers {
+    fn from(regs: user_regs_struct) -> Self {
+        Self(regs)
+    }
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+impl From<Registers> for user_regs_struct {
+    fn from(regs: Registers) -> Self {
+        regs.0
+    }
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+impl fmt::Debug for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registers")
+            .field("instruction_pointer", &self.instruction_pointer())
+            .field("stack_pointer", &self.stack_pointer())
+            .field("return_value", &self.return_value())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod tests {
+    use super::*;
+
+    fn zeroed() -> Registers {
+        Registers(unsafe { std::mem::zeroed() })
+    }
+
+    #[test]
+    fn accessors_round_trip() {
+        let mut regs = zeroed();
+        regs.set_instruction_pointer(0x1000);
+        regs.set_stack_pointer(0x2000);
+        regs.set_return_value(0x3000);
+        for n in 0..ARGUMENT_REGISTERS {
+            regs.set_argument(n, n + 1).unwrap();
+        }
+
+        assert_eq!(regs.instruction_pointer(), 0x1000);
+        assert_eq!(regs.stack_pointer(), 0x2000);
+        assert_eq!(regs.return_value(), 0x3000);
+        for n in 0..ARGUMENT_REGISTERS {
+            assert_eq!(regs.argument(n), Some(n + 1));
+        }
+    }
+
+    #[test]
+    fn arguments_follow_calling_convention() {
+        let mut regs = zeroed();
+        regs.set_argument(0, 0x1234).unwrap();
+
+        #[cfg(target_arch = "x86_64")]
+        assert_eq!(regs.raw().rdi, 0x1234);
+
+        #[cfg(target_arch = "aarch64")]
+        assert_eq!(regs.raw().regs[0], 0x1234);
+    }
+
+    #[test]
+    fn stack_arguments_are_rejected() {
+        let mut regs = zeroed();
+
+        assert_eq!(regs.argument(ARGUMENT_REGISTERS), None);
+        let e = regs.set_argument(ARGUMENT_REGISTERS, 0).unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::InvalidInput);
+    }
+}
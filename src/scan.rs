@@ -0,0 +1,377 @@
+use std::{
+    fmt, io,
+    mem::{align_of, size_of},
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    pod::{bytes_of, from_bytes},
+    MemoryRegion, Pod, Process,
+};
+
+/// How many bytes a scan reads at a time.
+const SCAN_CHUNK: usize = 0x10000;
+
+/// Which regions of a process' memory a scan looks through.
+///
+/// Unreadable regions are always skipped. By default, every other region is
+/// scanned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegionFilter {
+    /// Only scan writable regions.
+    pub writable: bool,
+    /// Only scan regions that aren't backed by a file (the heap, stack, etc.).
+    pub anonymous: bool,
+    /// Only scan addresses within this range.
+    pub range: Option<Range<usize>>,
+}
+
+impl RegionFilter {
+    /// Create a filter that allows every readable region.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only scan writable regions.
+    pub fn writable(mut self) -> Self {
+        self.writable = true;
+        self
+    }
+
+    /// Only scan regions that aren't backed by a file.
+    pub fn anonymous(mut self) -> Self {
+        self.anonymous = true;
+        self
+    }
+
+    /// Only scan addresses within `range`.
+    pub fn range(mut self, range: Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Returns the part of `region` that should be scanned, if any.
+    pub(crate) fn bounds(&self, region: &MemoryRegion) -> Option<Range<usize>> {
+        if !region.perms.read
+            || (self.writable && !region.perms.write)
+            || (self.anonymous && region.inode != 0)
+        {
+            return None;
+        }
+
+        let (start, end) = match &self.range {
+            Some(range) => (region.start.max(range.start), region.end.min(range.end)),
+            None => (region.start, region.end),
+        };
+
+        (start < end).then_some(start..end)
+    }
+}
+
+/// A value that `ScanCompare::ApproxEq` can measure the distance between.
+///
+/// Implemented for the primitive integers and floats.
+pub trait Distance: Sized + Copy {
+    /// Returns how far apart `self` and `other` are, or `None` if that's too
+    /// far to fit in `Self` (e.g. `i32::MAX` and `-5`).
+    fn distance(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_distance_int {
+    ($($ty:ty),*) => {
+        $(impl Distance for $ty {
+            fn distance(self, other: Self) -> Option<Self> {
+                if self >= other {
+                    self.checked_sub(other)
+                } else {
+                    other.checked_sub(self)
+                }
+            }
+        })*
+    };
+}
+
+macro_rules! impl_distance_float {
+    ($($ty:ty),*) => {
+        $(impl Distance for $ty {
+            fn distance(self, other: Self) -> Option<Self> {
+                Some((self - other).abs())
+            }
+        })*
+    };
+}
+
+impl_distance_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_distance_float!(f32, f64);
+
+/// How `Process::scan_compare` decides whether a value matches.
+///
+/// Values that aren't comparable with themselves (i.e. NaN) never match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScanCompare<T> {
+    /// Equal to the value.
+    Exact(T),
+    /// Strictly greater than the value.
+    GreaterThan(T),
+    /// Strictly less than the value.
+    LessThan(T),
+    /// Between the two values, inclusive.
+    Between(T, T),
+    /// Within `epsilon` (the second value) of the first value, inclusive.
+    /// Meant for floats.
+    ApproxEq(T, T),
+}
+
+impl<T: PartialOrd + Distance> ScanCompare<T> {
+    /// Returns whether `value` matches.
+    pub fn matches(&self, value: T) -> bool {
+        if value.partial_cmp(&value).is_none() {
+            return false;
+        }
+
+        match *self {
+            Self::Exact(target) => value == target,
+            Self::GreaterThan(target) => value > target,
+            Self::LessThan(target) => value < target,
+            Self::Between(low, high) => low <= value && value <= high,
+            Self::ApproxEq(target, epsilon) => value
+                .distance(target)
+                .is_some_and(|distance| distance <= epsilon),
+        }
+    }
+}
+
+/// Options for a scan.
+#[derive(Clone, Default)]
+pub struct ScanOptions<'a> {
+    /// Which regions to scan.
+    pub filter: RegionFilter,
+    /// Only check addresses that are a multiple of this. Defaults to the
+    /// alignment of the type being scanned for.
+    pub alignment: Option<usize>,
+    /// Stop the scan early once this is set. Checked before each region.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Called after each region with the number of bytes scanned so far, and
+    /// the total number of bytes to scan.
+    pub progress: Option<&'a dyn Fn(usize, usize)>,
+}
+
+impl<'a> ScanOptions<'a> {
+    /// Create the default options: scan every readable region, checking
+    /// addresses aligned for the type being scanned for.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only scan the regions allowed by `filter`.
+    pub fn filter(mut self, filter: RegionFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only check addresses that are a multiple of `alignment`. Use 1 to check
+    /// every address.
+    pub fn alignment(mut self, alignment: usize) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Stop the scan early once `cancel` is set, e.g. from another thread.
+    ///
+    /// A cancelled scan returns the matches it found so far.
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Call `progress` after each region with the number of bytes scanned so
+    /// far, and the total number of bytes to scan.
+    pub fn progress(mut self, progress: &'a dyn Fn(usize, usize)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+impl fmt::Debug for ScanOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScanOptions")
+            .field("filter", &self.filter)
+            .field("alignment", &self.alignment)
+            .field("cancel", &self.cancel)
+            .field("progress", &self.progress.map(|_| ".."))
+            .finish()
+    }
+}
+
+impl Process {
+    /// Searches every readable region of the process' memory for `needle`,
+    /// returning the address of every match.
+    ///
+    /// Only checks addresses aligned to `align_of::<T>()`.
+    pub fn scan_value<T: Pod>(&mut self, needle: T) -> io::Result<Vec<usize>> {
+        self.scan_value_with(needle, ScanOptions::new())
+    }
+
+    /// Searches the regions of the process' memory allowed by `filter` for
+    /// `needle`, returning the address of every match.
+    ///
+    /// Only checks addresses aligned to `align_of::<T>()`.
+    pub fn scan_value_in<T: Pod>(
+        &mut self,
+        needle: T,
+        filter: RegionFilter,
+    ) -> io::Result<Vec<usize>> {
+        self.scan_value_with(needle, ScanOptions::new().filter(filter))
+    }
+
+    /// Searches the process' memory for `needle` as described by `options`,
+    /// returning the address of every match.
+    ///
+    /// Parts of a region that can't be read are skipped.
+    pub fn scan_value_with<T: Pod>(
+        &mut self,
+        needle: T,
+        options: ScanOptions<'_>,
+    ) -> io::Result<Vec<usize>> {
+        let needle = bytes_of(&needle);
+        if needle.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.scan_with(needle.len(), align_of::<T>(), options, |window| {
+            window == needle
+        })
+    }
+
+    /// Searches every readable region of the process' memory for values that
+    /// match `cmp`, returning the address of every match.
+    ///
+    /// Only checks addresses aligned to `align_of::<T>()`.
+    pub fn scan_compare<T: PartialOrd + Distance + Pod>(
+        &mut self,
+        cmp: ScanCompare<T>,
+    ) -> io::Result<Vec<usize>> {
+        self.scan_compare_with(cmp, ScanOptions::new())
+    }
+
+    /// Searches the regions of the process' memory allowed by `filter` for
+    /// values that match `cmp`, returning the address of every match.
+    ///
+    /// Only checks addresses aligned to `align_of::<T>()`.
+    pub fn scan_compare_in<T: PartialOrd + Distance + Pod>(
+        &mut self,
+        cmp: ScanCompare<T>,
+        filter: RegionFilter,
+    ) -> io::Result<Vec<usize>> {
+        self.scan_compare_with(cmp, ScanOptions::new().filter(filter))
+    }
+
+    /// Searches the process' memory for values that match `cmp` as described
+    /// by `options`, returning the address of every match.
+    ///
+    /// Parts of a region that can't be read are skipped.
+    pub fn scan_compare_with<T: PartialOrd + Distance + Pod>(
+        &mut self,
+        cmp: ScanCompare<T>,
+        options: ScanOptions<'_>,
+    ) -> io::Result<Vec<usize>> {
+        if size_of::<T>() == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.scan_with(size_of::<T>(), align_of::<T>(), options, |window| {
+            cmp.matches(from_bytes(window))
+        })
+    }
+
+    /// Calls `matches` with every `size`-byte window allowed by `options`,
+    /// returning the address of every window it accepts.
+    ///
+    /// Windows start at multiples of `align`, unless `options` says otherwise.
+    /// Parts of a region that can't be read are skipped. `size` must not be 0.
+    fn scan_with(
+        &mut self,
+        size: usize,
+        align: usize,
+        options: ScanOptions<'_>,
+        mut matches: impl FnMut(&[u8]) -> bool,
+    ) -> io::Result<Vec<usize>> {
+        let alignment = options.alignment.unwrap_or(align).max(1);
+        let mut found = Vec::new();
+        let mut buf = vec![0; SCAN_CHUNK.max(size)];
+
+        let regions: Vec<_> = self
+            .regions()?
+            .iter()
+            .filter_map(|region| options.filter.bounds(region))
+            .collect();
+        let total = regions.iter().map(|bounds| bounds.end - bounds.start).sum();
+        let mut scanned = 0;
+
+        for bounds in regions {
+            if options
+                .cancel
+                .as_ref()
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            {
+                break;
+            }
+
+            let mut address = bounds.start;
+            while address + size <= bounds.end {
+                let len = buf.len().min(bounds.end - address);
+                if self.read_memory(address, &mut buf[..len]).is_ok() {
+                    let first = address.next_multiple_of(alignment) - address;
+                    found.extend(
+                        (first..=len - size)
+                            .step_by(alignment)
+                            .filter(|&i| matches(&buf[i..i + size]))
+                            .map(|i| address + i),
+                    );
+                }
+
+                if address + len == bounds.end {
+                    break;
+                }
+
+                // Overlap chunks, in case a match straddles two of them
+                address += len - (size - 1);
+            }
+
+            scanned += bounds.end - bounds.start;
+            if let Some(progress) = options.progress {
+                progress(scanned, total);
+            }
+        }
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approx_eq_matches_within_epsilon() {
+        assert!(ScanCompare::ApproxEq(1.0f32, 0.5).matches(1.25));
+        assert!(ScanCompare::ApproxEq(1.0f32, 0.5).matches(0.5));
+        assert!(!ScanCompare::ApproxEq(1.0f32, 0.5).matches(1.75));
+        assert!(!ScanCompare::ApproxEq(1.0f32, 0.5).matches(f32::NAN));
+        assert!(ScanCompare::ApproxEq(10u8, 3).matches(7));
+        assert!(!ScanCompare::ApproxEq(10u8, 3).matches(14));
+    }
+
+    #[test]
+    fn approx_eq_does_not_overflow() {
+        assert!(!ScanCompare::ApproxEq(-5i32, 2).matches(i32::MAX));
+        assert!(!ScanCompare::ApproxEq(i32::MAX, 2).matches(-5));
+        assert!(!ScanCompare::ApproxEq(i64::MIN, i64::MAX).matches(i64::MAX));
+        assert!(ScanCompare::ApproxEq(-1i64, i64::MAX).matches(i64::MAX - 1));
+        assert!(!ScanCompare::ApproxEq(0u8, 2).matches(u8::MAX));
+    }
+}
@@ -0,0 +1,164 @@
+use std::{io, marker::PhantomData, mem::size_of};
+
+use crate::{pod::from_bytes, Pod, Process, RegionFilter};
+
+/// How many bytes a snapshot reads at a time.
+const SNAPSHOT_CHUNK: usize = 0x10000;
+
+/// How a value has to have changed to pass `Snapshot::filter_changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The value is different (compared byte-for-byte).
+    Changed,
+    /// The value is the same (compared byte-for-byte).
+    Unchanged,
+    /// The value is greater than before.
+    Increased,
+    /// The value is less than before.
+    Decreased,
+}
+
+/// A run of consecutive candidate values, and their bytes when last read.
+#[derive(Debug, Clone)]
+struct Block {
+    address: usize,
+    data: Vec<u8>,
+}
+
+/// A copy of every candidate `T` in (part of) a process' memory, for finding
+/// a value you don't know by how it changes.
+///
+/// Take one with `Process::snapshot`, let the value change, then narrow it
+/// down with `Snapshot::filter_changed`. Repeat until only a few candidates
+/// are left.
+///
+/// Candidates are laid out every `size_of::<T>()` bytes from the start of
+/// each region, so they're aligned for primitive types.
+#[derive(Debug, Clone)]
+pub struct Snapshot<T> {
+    blocks: Vec<Block>,
+    _value: PhantomData<T>,
+}
+
+impl<T: Pod + PartialOrd> Snapshot<T> {
+    /// Returns a new snapshot of only the candidates that changed as described
+    /// by `kind` since this one was taken, holding their current values.
+    ///
+    /// Candidates that can't be read anymore are dropped.
+    pub fn filter_changed(&self, proc: &mut Process, kind: ChangeKind) -> io::Result<Self> {
+        let size = size_of::<T>();
+        let mut blocks = Vec::new();
+        let mut buf = Vec::new();
+
+        for block in &self.blocks {
+            buf.resize(block.data.len(), 0);
+            if proc.read_memory(block.address, &mut buf).is_err() {
+                continue;
+            }
+
+            let mut run: Option<Block> = None;
+            for (i, (old, new)) in block
+                .data
+                .chunks_exact(size)
+                .zip(buf.chunks_exact(size))
+                .enumerate()
+            {
+                let keep = match kind {
+                    ChangeKind::Changed => old != new,
+                    ChangeKind::Unchanged => old == new,
+                    ChangeKind::Increased => from_bytes::<T>(new) > from_bytes::<T>(old),
+                    ChangeKind::Decreased => from_bytes::<T>(new) < from_bytes::<T>(old),
+                };
+
+                if keep {
+                    run.get_or_insert_with(|| Block {
+                        address: block.address + i * size,
+                        data: Vec::new(),
+                    })
+                    .data
+                    .extend_from_slice(new);
+                } else if let Some(run) = run.take() {
+                    blocks.push(run);
+                }
+            }
+
+            blocks.extend(run);
+        }
+
+        Ok(Self {
+            blocks,
+            _value: PhantomData,
+        })
+    }
+
+    /// Returns how many candidates are left.
+    pub fn len(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.data.len() / size_of::<T>())
+            .sum()
+    }
+
+    /// Returns whether there are no candidates left.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns an iterator over the address of each candidate, and its value
+    /// when the snapshot was taken.
+    pub fn values(&self) -> impl Iterator<Item = (usize, T)> + '_ {
+        self.blocks.iter().flat_map(|block| {
+            block
+                .data
+                .chunks_exact(size_of::<T>())
+                .enumerate()
+                .map(|(i, bytes)| (block.address + i * size_of::<T>(), from_bytes(bytes)))
+        })
+    }
+}
+
+impl Process {
+    /// Takes a snapshot of every `T` in the writable regions of the process'
+    /// memory.
+    pub fn snapshot<T: Pod>(&mut self) -> io::Result<Snapshot<T>> {
+        self.snapshot_in(RegionFilter::new().writable())
+    }
+
+    /// Takes a snapshot of every `T` in the regions of the process' memory
+    /// allowed by `filter`.
+    ///
+    /// Parts of a region that can't be read are skipped.
+    pub fn snapshot_in<T: Pod>(&mut self, filter: RegionFilter) -> io::Result<Snapshot<T>> {
+        let size = size_of::<T>();
+        let mut blocks = Vec::new();
+        if size == 0 {
+            return Ok(Snapshot {
+                blocks,
+                _value: PhantomData,
+            });
+        }
+
+        let chunk = (SNAPSHOT_CHUNK / size).max(1) * size;
+        for region in self.regions()? {
+            let Some(bounds) = filter.bounds(&region) else {
+                continue;
+            };
+
+            let mut address = bounds.start;
+            while address + size <= bounds.end {
+                let len = chunk.min((bounds.end - address) / size * size);
+                let mut data = vec![0; len];
+                if self.read_memory(address, &mut data).is_ok() {
+                    blocks.push(Block { address, data });
+                }
+
+                address += len;
+            }
+        }
+
+        Ok(Snapshot {
+            blocks,
+            _value: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs::read_to_string,
+    io::{self, ErrorKind},
+    str::FromStr,
+};
+
+use crate::{Pid, Process};
+
+/// The well-known fields of `/proc/<pid>/stat`.
+///
+/// Created via `Process::stat`. Times are in clock ticks (see
+/// `sysconf(_SC_CLK_TCK)`, usually 100 per second). See `proc(5)` for the
+/// details of each field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcStat {
+    pub pid: Pid,
+    /// The executable's name, as in `/proc/<pid>/comm` (up to 15 bytes).
+    pub comm: String,
+    /// The process state, e.g. `R` (running), `S` (sleeping), `T`
+    /// (stopped), `t` (tracing stop), or `Z` (zombie).
+    pub state: char,
+    pub ppid: Pid,
+    pub pgrp: i32,
+    pub session: i32,
+    pub tty_nr: i32,
+    pub tpgid: i32,
+    pub flags: u32,
+    pub minflt: u64,
+    pub majflt: u64,
+    /// Time spent in user mode.
+    pub utime: u64,
+    /// Time spent in kernel mode.
+    pub stime: u64,
+    /// Time waited-for children spent in user mode.
+    pub cutime: i64,
+    /// Time waited-for children spent in kernel mode.
+    pub cstime: i64,
+    pub priority: i64,
+    pub nice: i64,
+    pub num_threads: i64,
+    /// When the process started, after system boot.
+    pub starttime: u64,
+    /// Virtual memory size, in bytes.
+    pub vsize: u64,
+    /// Resident set size, in pages.
+    pub rss: i64,
+}
+
+impl ProcStat {
+    /// Returns whether the process is a zombie (it has exited, but hasn't
+    /// been reaped yet).
+    pub fn is_zombie(&self) -> bool {
+        self.state == 'Z'
+    }
+
+    fn parse(data: &str) -> Option<Self> {
+        // The name is in parentheses, and can contain both spaces and
+        // parentheses itself, so it ends at the *last* `)`
+        let (pid, rest) = data.split_once(" (")?;
+        let (comm, rest) = rest.rsplit_once(") ")?;
+
+        let mut fields = rest.split_whitespace();
+        let state = fields.next()?.chars().next()?;
+
+        fn next<T: FromStr>(fields: &mut std::str::SplitWhitespace) -> Option<T> {
+            fields.next()?.parse().ok()
+        }
+
+        let ppid = next(&mut fields)?;
+        let pgrp = next(&mut fields)?;
+        let session = next(&mut fields)?;
+        let tty_nr = next(&mut fields)?;
+        let tpgid = next(&mut fields)?;
+        let flags = next(&mut fields)?;
+        let minflt = next(&mut fields)?;
+        let _cminflt: u64 = next(&mut fields)?;
+        let majflt = next(&mut fields)?;
+        let _cmajflt: u64 = next(&mut fields)?;
+        let utime = next(&mut fields)?;
+        let stime = next(&mut fields)?;
+        let cutime = next(&mut fields)?;
+        let cstime = next(&mut fields)?;
+        let priority = next(&mut fields)?;
+        let nice = next(&mut fields)?;
+        let num_threads = next(&mut fields)?;
+        let _itrealvalue: i64 = next(&mut fields)?;
+        let starttime = next(&mut fields)?;
+        let vsize = next(&mut fields)?;
+        let rss = next(&mut fields)?;
+
+        Some(Self {
+            pid: Pid::from_raw(pid.parse().ok()?),
+            comm: comm.to_string(),
+            state,
+            ppid: Pid::from_raw(ppid),
+            pgrp,
+            session,
+            tty_nr,
+            tpgid,
+            flags,
+            minflt,
+            majflt,
+            utime,
+            stime,
+            cutime,
+            cstime,
+            priority,
+            nice,
+            num_threads,
+            starttime,
+            vsize,
+            rss,
+        })
+    }
+}
+
+impl Process {
+    /// Reads the attached process' status from `/proc/<pid>/stat`.
+    ///
+    /// Useful e.g. for checking whether it's a zombie before trying to
+    /// operate on it.
+    pub fn stat(&self) -> io::Result<ProcStat> {
+        let file = format!("/proc/{}/stat", self.pid);
+        let data = read_to_string(&file)?;
+
+        ProcStat::parse(data.trim_end())
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, format!("Bad format in {file}")))
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fs::read,
+    io::{self, ErrorKind},
+};
+
+use object::{Object, ObjectSegment, ObjectSymbol};
+
+use crate::Process;
+
+impl Process {
+    /// Returns the runtime address of `symbol` in `module`.
+    ///
+    /// `module` is matched the same way as in `Process::module_base`. The
+    /// module's ELF file is loaded from disk, and both its static symbol table
+    /// (`.symtab`) and dynamic symbol table (`.dynsym`) are searched. Stripped
+    /// binaries may have neither entry for a given symbol, in which case
+    /// `ErrorKind::NotFound` is returned.
+    pub fn symbol_address(&mut self, module: &str, symbol: &str) -> io::Result<usize> {
+        let (base, path) = self
+            .module_first_region(module)?
+            .and_then(|region| Some((region.start, region.path?)))
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::NotFound,
+                    format!("Failed to find module `{module}`"),
+                )
+            })?;
+
+        let data = read(&path)?;
+        let file = object::File::parse(&*data).map_err(|e| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Failed to parse `{path}`: {e}"),
+            )
+        })?;
+
+        let address = file
+            .symbols()
+            .chain(file.dynamic_symbols())
+            .find(|sym| sym.is_definition() && sym.name() == Ok(symbol))
+            .map(|sym| sym.address())
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::NotFound,
+                    format!("Failed to find symbol `{symbol}` in `{path}`"),
+                )
+            })?;
+
+        // The module is mapped starting at its lowest loadable segment
+        // (rounded down to a page), which is 0 for PIEs and shared objects
+        // but not for plain executables.
+        let load_base = file
+            .segments()
+            .map(|segment| segment.address())
+            .min()
+            .unwrap_or(0)
+            & !(self.page_size() as u64 - 1);
+
+        address
+            .checked_sub(load_base)
+            .and_then(|offset| base.checked_add(offset as usize))
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Symbol `{symbol}` is outside of `{path}`"),
+                )
+            })
+    }
+}
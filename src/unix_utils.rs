@@ -0,0 +1,289 @@
+use std::{
+    fs::read_to_string,
+    io::{self, ErrorKind, IoSlice, IoSliceMut},
+    os::raw::c_void,
+};
+
+#[cfg(target_arch = "x86_64")]
+use std::{mem::offset_of, ptr};
+
+#[cfg(target_arch = "x86_64")]
+use nix::libc;
+use nix::sys::{
+    ptrace,
+    signal::{self, Signal},
+    uio::{process_vm_readv, process_vm_writev, RemoteIoVec},
+    wait::{waitpid, WaitPidFlag},
+};
+
+#[cfg(target_arch = "x86_64")]
+use crate::regs::user_fpregs_struct;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+use crate::regs::user_regs_struct;
+use crate::{backend::Backend, get_process_status_name, Errno, Pid, WaitStatus};
+
+/// The Linux backend, built on ptrace and `/proc`.
+pub(crate) struct Unix;
+
+impl Backend for Unix {
+    type Handle = Pid;
+
+    fn attach(pid: Pid) -> io::Result<Pid> {
+        let pid = Self::attach_stopped(pid)?;
+        Self::cont(pid, None)?;
+
+        Ok(pid)
+    }
+
+    fn attach_stopped(pid: Pid) -> io::Result<Pid> {
+        ptrace::attach(pid)?;
+        Self::wait(pid)?;
+
+        Ok(pid)
+    }
+
+    fn seize(pid: Pid, options: ptrace::Options) -> io::Result<Pid> {
+        ptrace::seize(pid, options)?;
+
+        Ok(pid)
+    }
+
+    fn interrupt(pid: Pid) -> io::Result<()> {
+        ptrace::interrupt(pid).map_err(Errno::into)
+    }
+
+    fn stop(pid: Pid) -> io::Result<()> {
+        signal::kill(pid, Signal::SIGSTOP)?;
+        Self::wait(pid)?;
+
+        Ok(())
+    }
+
+    fn cont(pid: Pid, sig: Option<Signal>) -> io::Result<()> {
+        ptrace::cont(pid, sig).map_err(Errno::into)
+    }
+
+    fn syscall(pid: Pid, sig: Option<Signal>) -> io::Result<()> {
+        ptrace::syscall(pid, sig).map_err(Errno::into)
+    }
+
+    fn step(pid: Pid, sig: Option<Signal>) -> io::Result<()> {
+        ptrace::step(pid, sig).map_err(Errno::into)
+    }
+
+    fn wait(pid: Pid) -> io::Result<WaitStatus> {
+        // `__WALL` so this works for threads as well as processes
+        Ok(waitpid(pid, Some(WaitPidFlag::__WALL))?)
+    }
+
+    fn set_options(pid: Pid, options: ptrace::Options) -> io::Result<()> {
+        ptrace::setoptions(pid, options).map_err(Errno::into)
+    }
+
+    fn detach(pid: Pid, sig: Option<Signal>) -> io::Result<()> {
+        ptrace::detach(pid, sig).map_err(Errno::into)
+    }
+
+    fn kill(pid: Pid) -> io::Result<()> {
+        signal::kill(pid, Signal::SIGKILL)?;
+        loop {
+            match Self::wait(pid)? {
+                WaitStatus::Exited(..) | WaitStatus::Signaled(..) => return Ok(()),
+                _ => continue,
+            }
+        }
+    }
+
+    fn read_word(pid: Pid, address: usize) -> io::Result<isize> {
+        let addr = address as *mut c_void;
+
+        let data = ptrace::read(pid, addr)? as isize;
+        Ok(data)
+    }
+
+    fn read_memory(pid: Pid, address: usize, buf: &mut [u8]) -> io::Result<usize> {
+        let remote = [RemoteIoVec {
+            base: address,
+            len: buf.len(),
+        }];
+
+        Ok(process_vm_readv(pid, &mut [IoSliceMut::new(buf)], &remote)?)
+    }
+
+    fn read_memory_many(
+        pid: Pid,
+        addresses: &[usize],
+        len: usize,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        let remote: Vec<_> = addresses
+            .iter()
+            .map(|&base| RemoteIoVec { base, len })
+            .collect();
+
+        Ok(process_vm_readv(pid, &mut [IoSliceMut::new(buf)], &remote)?)
+    }
+
+    fn write_word(pid: Pid, address: usize, data: isize) -> io::Result<()> {
+        let addr = address as *mut c_void;
+
+        // `PTRACE_POKEDATA` takes the word itself in place of a pointer, so
+        // this just reinterprets its bits. It's never dereferenced, so the
+        // cast is fine for any value, including negative ones (unlike pointer
+        // arithmetic, which must stay in bounds).
+        let data = data as *mut c_void;
+
+        unsafe {
+            ptrace::write(pid, addr, data)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_memory(pid: Pid, address: usize, buf: &[u8]) -> io::Result<usize> {
+        let remote = [RemoteIoVec {
+            base: address,
+            len: buf.len(),
+        }];
+
+        Ok(process_vm_writev(pid, &[IoSlice::new(buf)], &remote)?)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn get_regs(pid: Pid) -> io::Result<user_regs_struct> {
+        Ok(ptrace::getregs(pid)?)
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn get_regs(pid: Pid) -> io::Result<user_regs_struct> {
+        aarch64::get_regs(pid)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn set_regs(pid: Pid, regs: user_regs_struct) -> io::Result<()> {
+        Ok(ptrace::setregs(pid, regs)?)
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    fn set_regs(pid: Pid, regs: user_regs_struct) -> io::Result<()> {
+        aarch64::set_regs(pid, regs)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn get_fpregs(pid: Pid) -> io::Result<user_fpregs_struct> {
+        let mut regs = unsafe { std::mem::zeroed::<user_fpregs_struct>() };
+        Errno::result(unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETFPREGS,
+                pid.as_raw(),
+                ptr::null_mut::<c_void>(),
+                &mut regs as *mut user_fpregs_struct as *mut c_void,
+            )
+        })?;
+
+        Ok(regs)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn set_fpregs(pid: Pid, mut regs: user_fpregs_struct) -> io::Result<()> {
+        Errno::result(unsafe {
+            libc::ptrace(
+                libc::PTRACE_SETFPREGS,
+                pid.as_raw(),
+                ptr::null_mut::<c_void>(),
+                &mut regs as *mut user_fpregs_struct as *mut c_void,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn read_debug_register(pid: Pid, register: usize) -> io::Result<usize> {
+        Ok(ptrace::read_user(pid, debug_register_offset(register))? as usize)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn write_debug_register(pid: Pid, register: usize, value: usize) -> io::Result<()> {
+        unsafe {
+            ptrace::write_user(pid, debug_register_offset(register), value as *mut c_void)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_base(pid: Pid) -> io::Result<usize> {
+        let data = read_to_string(format!("/proc/{pid}/maps"))?;
+        let line = data.lines().next().ok_or(Errno::ENOKEY)?;
+        let (base, _) = line.split_once('-').ok_or(Errno::ENOKEY)?;
+
+        usize::from_str_radix(base, 16).map_err(|_| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Bad format in /proc/{pid}/maps"),
+            )
+        })
+    }
+
+    fn get_name(pid: Pid) -> io::Result<String> {
+        get_process_status_name(&format!("/proc/{pid}/status"))
+    }
+}
+
+/// Returns where debug register `register` lives in the kernel's `struct user`,
+/// which is what `PTRACE_PEEKUSER` and `PTRACE_POKEUSER` index into.
+#[cfg(target_arch = "x86_64")]
+fn debug_register_offset(register: usize) -> *mut c_void {
+    (offset_of!(libc::user, u_debugreg) + register * 8) as *mut c_void
+}
+
+/// aarch64 has no `PTRACE_GETREGS`, so registers are accessed through the
+/// `NT_PRSTATUS` regset instead.
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use std::{io, mem::size_of};
+
+    use nix::{
+        errno::Errno,
+        libc::{self, c_void, iovec},
+    };
+
+    use crate::{regs::user_regs_struct, Pid};
+
+    pub(super) fn get_regs(tid: Pid) -> io::Result<user_regs_struct> {
+        let mut regs = unsafe { std::mem::zeroed::<user_regs_struct>() };
+        let mut iov = iovec {
+            iov_base: &mut regs as *mut _ as *mut c_void,
+            iov_len: size_of::<user_regs_struct>(),
+        };
+
+        Errno::result(unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGSET,
+                tid.as_raw(),
+                libc::NT_PRSTATUS as usize as *mut c_void,
+                &mut iov as *mut iovec as *mut c_void,
+            )
+        })?;
+
+        Ok(regs)
+    }
+
+    pub(super) fn set_regs(tid: Pid, mut regs: user_regs_struct) -> io::Result<()> {
+        let mut iov = iovec {
+            iov_base: &mut regs as *mut _ as *mut c_void,
+            iov_len: size_of::<user_regs_struct>(),
+        };
+
+        Errno::result(unsafe {
+            libc::ptrace(
+                libc::PTRACE_SETREGSET,
+                tid.as_raw(),
+                libc::NT_PRSTATUS as usize as *mut c_void,
+                &mut iov as *mut iovec as *mut c_void,
+            )
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use std::io::{self, ErrorKind};
+
+#[cfg(target_arch = "x86_64")]
+use crate::backend::Handle;
+use crate::Process;
+
+/// What kind of access triggers a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Triggers on reads. x86 can't watch for reads alone, so this also
+    /// triggers on writes (the same as `WatchKind::ReadWrite`).
+    Read,
+    /// Triggers on writes.
+    Write,
+    /// Triggers on reads and writes.
+    ReadWrite,
+}
+
+/// How many bytes a watchpoint covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchLen {
+    One,
+    Two,
+    Four,
+    Eight,
+}
+
+impl WatchLen {
+    /// Returns the number of bytes covered.
+    pub fn bytes(self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+            Self::Four => 4,
+            Self::Eight => 8,
+        }
+    }
+}
+
+/// A hardware watchpoint set in a process, using one of the debug registers.
+///
+/// Clears its debug register when dropped. The process has to be stopped for
+/// that to work; if it isn't (or it's already gone), the register is left as-is.
+/// Created via `Process::set_watchpoint`.
+#[derive(Debug)]
+pub struct Watchpoint {
+    /// The thread whose debug registers are used.
+    #[cfg(target_arch = "x86_64")]
+    thread: Handle,
+    slot: usize,
+    address: usize,
+}
+
+impl Watchpoint {
+    /// Returns the address being watched.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    /// Returns which debug register (DR0-DR3) the watchpoint uses.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+}
+
+impl Drop for Watchpoint {
+    fn drop(&mut self) {
+        #[cfg(target_arch = "x86_64")]
+        let _ = x86_64::clear(self.thread, self.slot);
+    }
+}
+
+impl Process {
+    /// Sets a hardware watchpoint on `len` bytes at `address`, using the first
+    /// free debug register (DR0-DR3). Stops the process first.
+    ///
+    /// `address` must be aligned to `len`. Debug registers are per-thread; this
+    /// only watches the thread chosen with `Process::select_thread` (the main
+    /// thread by default). Only available on x86_64; fails with
+    /// `ErrorKind::Unsupported` elsewhere.
+    pub fn set_watchpoint(
+        &mut self,
+        address: usize,
+        len: WatchLen,
+        kind: WatchKind,
+    ) -> io::Result<Watchpoint> {
+        if address % len.bytes() != 0 {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Watchpoint address 0x{address:x} isn't aligned to {} bytes",
+                    len.bytes()
+                ),
+            ));
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            self.stop()?;
+            let thread = self.thread_handle();
+            let slot = x86_64::set(thread, address, len, kind)?;
+
+            Ok(Watchpoint {
+                thread,
+                slot,
+                address,
+            })
+        }
+
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            let _ = kind;
+            Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "Hardware watchpoints are only supported on x86_64",
+            ))
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use std::io;
+
+    use super::{WatchKind, WatchLen};
+    use crate::backend::{Backend, Handle, Platform};
+
+    /// The debug control register.
+    const DR7: usize = 7;
+
+    fn read(thread: Handle, register: usize) -> io::Result<usize> {
+        Platform::read_debug_register(thread, register)
+    }
+
+    fn write(thread: Handle, register: usize, value: usize) -> io::Result<()> {
+        Platform::write_debug_register(thread, register, value)
+    }
+
+    /// Returns the DR7 bits controlling `slot`'s condition and length.
+    fn control_mask(slot: usize) -> usize {
+        0b1111 << (16 + slot * 4)
+    }
+
+    fn enable_bit(slot: usize) -> usize {
+        1 << (slot * 2)
+    }
+
+    pub(super) fn set(
+        thread: Handle,
+        address: usize,
+        len: WatchLen,
+        kind: WatchKind,
+    ) -> io::Result<usize> {
+        let mut dr7 = read(thread, DR7)?;
+        let slot = (0..4)
+            .find(|slot| dr7 & enable_bit(*slot) == 0)
+            .ok_or_else(|| io::Error::other("All debug registers are in use"))?;
+
+        let rw = match kind {
+            WatchKind::Write => 0b01,
+            WatchKind::Read | WatchKind::ReadWrite => 0b11,
+        };
+        let len = match len {
+            WatchLen::One => 0b00,
+            WatchLen::Two => 0b01,
+            WatchLen::Four => 0b11,
+            WatchLen::Eight => 0b10,
+        };
+
+        write(thread, slot, address)?;
+
+        dr7 &= !control_mask(slot);
+        dr7 |= (rw | len << 2) << (16 + slot * 4);
+        dr7 |= enable_bit(slot);
+        write(thread, DR7, dr7)?;
+
+        Ok(slot)
+    }
+
+    pub(super) fn clear(thread: Handle, slot: usize) -> io::Result<()> {
+        let mut dr7 = read(thread, DR7)?;
+        dr7 &= !(control_mask(slot) | enable_bit(slot));
+        write(thread, DR7, dr7)?;
+
+        write(thread, slot, 0)
+    }
+}
@@ -1,9 +1,9 @@
 use std::{
-    io::{self, Write},
+    io::{self, ErrorKind, Write},
     ops::{Deref, DerefMut},
 };
 
-use crate::Process;
+use crate::{offset_address, Process};
 
 /// A writer for a process.
 ///
@@ -32,20 +32,18 @@ impl<'a> ProcessWriter<'a> {
         }
     }
 
-    /// Create a new process writer. Advances by default.
-    pub fn offset(proc: &'a mut Process, offset: isize) -> Self {
-        let base = proc.base.unwrap();
-        let address = if offset >= 0 {
-            base + offset as usize
-        } else {
-            base - offset as usize
-        };
-        Self {
+    /// Create a new process writer at `offset`. Advances by default.
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if the resulting address is out of
+    /// range.
+    pub fn offset(proc: &'a mut Process, offset: isize) -> io::Result<Self> {
+        let address = offset_address(proc.base()?, offset)?;
+        Ok(Self {
             proc,
             address,
             data: Vec::new(),
             advance: true,
-        }
+        })
     }
 
     /// Disables advancing through memory.
@@ -60,18 +58,59 @@ impl<'a> ProcessWriter<'a> {
         self
     }
 
+    /// Returns the address the next flush will write to.
+    pub fn position(&self) -> usize {
+        self.address
+    }
+
+    /// Returns how many bytes have been written but not flushed yet.
+    pub fn pending(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Throws away the bytes that have been written but not flushed yet.
+    ///
+    /// Use this after a failed flush to drop the writer without it trying
+    /// (and panicking) again.
+    pub fn discard(&mut self) {
+        self.data.clear();
+    }
+
     /// Jumps to an address in memory.
     pub fn goto(&mut self, address: usize) {
         self.address = address;
     }
-    
+
     /// Jumps to an offset in memory.
-    pub fn goto_offset(&mut self, offset: isize) {
-        self.address = if offset >= 0 {
-            self.proc.base().unwrap() + offset as usize
-        } else {
-            self.proc.base().unwrap() - offset as usize
-        };
+    ///
+    /// Fails with `ErrorKind::InvalidInput` if the resulting address is out of
+    /// range, leaving the position unchanged.
+    pub fn goto_offset(&mut self, offset: isize) -> io::Result<()> {
+        self.address = offset_address(self.proc.base()?, offset)?;
+        Ok(())
+    }
+
+    /// Flushes the writer, then reads the written bytes back to make sure
+    /// they actually landed.
+    ///
+    /// Fails with `ErrorKind::InvalidData` if they don't match (e.g. because
+    /// something else overwrote them in the meantime).
+    pub fn flush_verified(&mut self) -> io::Result<()> {
+        let address = self.address;
+        let expected = self.data.clone();
+        self.flush()?;
+
+        let found = self.proc.read_vec(address, expected.len())?;
+        if found != expected {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Write to 0x{address:x} didn't land: expected {expected:02x?}, found {found:02x?}"
+                ),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -103,7 +142,7 @@ impl<'a> Write for ProcessWriter<'a> {
                 }
 
                 let mut source = self.proc.read_word(self.address + wordi * 8)?;
-                source &= isize::MAX << (difference * 8);
+                source &= !0 << (difference * 8);
                 word |= source;
 
                 self.proc.write_word(self.address + wordi * 8, word)?;
@@ -0,0 +1,34 @@
+mod common;
+
+use common::{writable_address, Target};
+
+#[test]
+fn write_many_restores_running_process() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.cont().unwrap();
+
+    proc.write_many(&[(address, 1u32), (address + 8, 2)])
+        .unwrap();
+
+    assert!(!proc.is_stopped());
+    assert_ne!(proc.stat().unwrap().state, 't');
+    assert_eq!(
+        proc.read_many::<u32>(&[address, address + 8]).unwrap(),
+        [1, 2]
+    );
+}
+
+#[test]
+fn write_many_leaves_stopped_process_stopped() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+
+    proc.write_many(&[(address, 3u64)]).unwrap();
+
+    assert!(proc.is_stopped());
+    assert_eq!(proc.stat().unwrap().state, 't');
+    assert_eq!(proc.read_many::<u64>(&[address]).unwrap(), [3]);
+}
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+use std::{
+    fs::read_to_string,
+    process::{Child, Command},
+    thread::sleep,
+    time::Duration,
+};
+
+use memmod::{Pid, Process, ProcessBuilder};
+
+/// A `sleep` process to attach to, killed when dropped.
+pub struct Target {
+    child: Child,
+}
+
+impl Target {
+    /// Spawns the target, and waits for it to start sleeping (attaching any
+    /// earlier could catch it mid-`execve`, which traps).
+    pub fn spawn() -> Self {
+        let child = Command::new("sleep").arg("100").spawn().unwrap();
+        let target = Self { child };
+
+        while !read_to_string(format!("/proc/{}/stat", target.pid()))
+            .unwrap()
+            .contains("(sleep) S ")
+        {
+            sleep(Duration::from_millis(10));
+        }
+
+        target
+    }
+
+    pub fn pid(&self) -> Pid {
+        Pid::from_raw(self.child.id() as i32)
+    }
+
+    /// Kills the target with `SIGKILL`, without reaping it.
+    pub fn kill(&mut self) {
+        self.child.kill().unwrap();
+    }
+
+    /// Attaches to the target, leaving it stopped.
+    pub fn attach(&self) -> Process {
+        ProcessBuilder::new()
+            .stop_on_attach(true)
+            .build(self.pid())
+            .unwrap()
+    }
+}
+
+impl Drop for Target {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Returns the start of the first writable mapping in the process.
+pub fn writable_address(proc: &mut Process) -> usize {
+    proc.regions()
+        .unwrap()
+        .into_iter()
+        .find(|region| region.perms.read && region.perms.write)
+        .unwrap()
+        .start
+}
@@ -0,0 +1,61 @@
+#![cfg(target_arch = "x86_64")]
+
+mod common;
+
+use std::io::ErrorKind;
+
+use common::{writable_address, Target};
+use memmod::Perms;
+
+#[test]
+fn protect_returns_previous_perms() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    let original = proc.which_region(address).unwrap().unwrap().perms;
+
+    let previous = proc
+        .protect(address, 1, Perms::new(true, false, false))
+        .unwrap();
+    assert_eq!(previous, original);
+    assert!(!proc.which_region(address).unwrap().unwrap().perms.write);
+
+    let previous = proc.protect(address, 1, original).unwrap();
+    assert_eq!(previous, Perms::new(true, false, false));
+    assert!(proc.which_region(address).unwrap().unwrap().perms.write);
+}
+
+#[test]
+fn protect_rejects_overflowing_range() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+
+    let e = proc
+        .protect(address, usize::MAX, Perms::new(true, true, false))
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn alloc_is_visible_to_cached_lookups() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    assert!(proc.is_readable(address).unwrap());
+
+    let allocated = proc.alloc(1, Perms::new(true, true, false)).unwrap();
+
+    assert!(proc.is_readable(allocated).unwrap());
+}
+
+#[test]
+fn alloc_rejects_overflowing_size() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let e = proc
+        .alloc(usize::MAX, Perms::new(true, true, false))
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use std::io::ErrorKind;
+
+use common::Target;
+
+#[test]
+fn offsets_are_relative_to_base() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let base = proc.base().unwrap();
+
+    assert_eq!(
+        proc.read_word_offset(0x10).unwrap(),
+        proc.read_word(base + 0x10).unwrap()
+    );
+    assert_eq!(proc.reader_offset(0x10, 8).unwrap().position(), base + 0x10);
+}
+
+#[test]
+fn overflowing_offsets_fail() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let e = proc.read_word_offset(usize::MAX).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+    let e = proc.write_word_offset(usize::MAX, 0).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+    let e = proc.reader_offset(isize::MIN, 8).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+    let e = proc.writer_offset(isize::MIN).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn overflowing_goto_offset_leaves_position() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let mut reader = proc.reader_offset(0, 8).unwrap();
+    let position = reader.position();
+
+    let e = reader.goto_offset(isize::MIN).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+    assert_eq!(reader.position(), position);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::io::ErrorKind;
+
+use common::{writable_address, Target};
+
+#[test]
+fn pointer_chain_follows_offsets() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.write_all_at(address, &(address + 0x20).to_ne_bytes())
+        .unwrap();
+
+    assert_eq!(
+        proc.pointer_chain(address, &[-0x10]).unwrap(),
+        address + 0x10
+    );
+    assert_eq!(
+        proc.pointer_chain(address, &[0x10]).unwrap(),
+        address + 0x30
+    );
+}
+
+#[test]
+fn pointer_chain_fails_instead_of_wrapping() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+
+    proc.write_all_at(address, &0x10usize.to_ne_bytes())
+        .unwrap();
+    let e = proc.pointer_chain(address, &[-0x20]).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+
+    proc.write_all_at(address, &usize::MAX.to_ne_bytes())
+        .unwrap();
+    let e = proc.pointer_chain(address, &[1]).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn deref_reads_target_pointer_width() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.write_all_at(address, &0x1122_3344_5566_7788u64.to_le_bytes())
+        .unwrap();
+
+    assert_eq!(proc.target_pointer_width().unwrap(), 8);
+    assert_eq!(proc.deref(address).unwrap(), 0x1122_3344_5566_7788);
+
+    // Pretend it's a 32-bit process, which only has the low half
+    proc.set_target_pointer_width(4);
+    assert_eq!(proc.deref(address).unwrap(), 0x5566_7788);
+    assert_eq!(proc.pointer_chain(address, &[0x10]).unwrap(), 0x5566_7798);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::{fs::read_to_string, path::Path, thread::sleep, time::Duration};
+
+use common::Target;
+use memmod::Process;
+
+/// Reads a process' state from `/proc/<pid>/stat`, without attaching, once
+/// it's done running (i.e. it's asleep or stopped).
+fn state(target: &Target) -> char {
+    loop {
+        let stat = read_to_string(format!("/proc/{}/stat", target.pid())).unwrap();
+        let (_, rest) = stat.rsplit_once(") ").unwrap();
+        match rest.chars().next().unwrap() {
+            'R' => sleep(Duration::from_millis(10)),
+            state => return state,
+        }
+    }
+}
+
+#[test]
+fn is_alive_is_false_for_zombies() {
+    let mut target = Target::spawn();
+    let proc = target.attach();
+    assert!(proc.is_alive());
+
+    // Nothing reaps it yet, so it lingers as a zombie
+    target.kill();
+    while !proc.stat().unwrap().is_zombie() {
+        sleep(Duration::from_millis(10));
+    }
+
+    assert!(!proc.is_alive());
+    let _ = proc.detach();
+}
+
+#[test]
+fn detach_resumes_process() {
+    let target = Target::spawn();
+    let proc = target.attach();
+
+    proc.detach().unwrap();
+
+    assert_eq!(state(&target), 'S');
+}
+
+#[test]
+fn detach_stopped_leaves_process_stopped() {
+    let target = Target::spawn();
+    let proc = target.attach();
+
+    proc.detach_stopped().unwrap();
+
+    assert_eq!(state(&target), 'T');
+}
+
+#[test]
+fn kill_reaps_process() {
+    let target = Target::spawn();
+    let proc = target.attach();
+
+    proc.kill().unwrap();
+
+    assert!(!Path::new(&format!("/proc/{}", target.pid())).exists());
+}
+
+#[test]
+fn process_walks_find_target() {
+    let target = Target::spawn();
+    let pid = target.pid();
+
+    assert!(memmod::list_processes()
+        .unwrap()
+        .contains(&(pid, "sleep".to_string())));
+    assert!(Process::find_all("slee").unwrap().contains(&pid));
+    assert!(memmod::pid_of("slee").unwrap().is_some());
+    assert!(memmod::pid_of_strict("sleep").unwrap().is_some());
+    assert_eq!(memmod::pid_of_strict("slee").unwrap(), None);
+}
@@ -0,0 +1,18 @@
+mod common;
+
+use common::{writable_address, Target};
+
+#[test]
+fn iter_values_advances_reader() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.write_array(address, &[1u32, 2, 3]).unwrap();
+
+    let mut reader = proc.reader(address, 14).unwrap();
+    let values: Vec<u32> = reader.iter_values().collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(values, [1, 2, 3]);
+    assert_eq!(reader.position(), address + 12);
+    assert_eq!(reader.remaining(), 2);
+}
@@ -0,0 +1,43 @@
+#![cfg(target_arch = "x86_64")]
+
+mod common;
+
+use common::Target;
+
+#[test]
+fn set_regs_round_trips() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let mut regs = proc.get_regs().unwrap();
+    let original = regs;
+    regs.rax = 0xdead_beef_cafe_f00d;
+    proc.set_regs(regs).unwrap();
+
+    assert_eq!(proc.get_regs().unwrap().rax, 0xdead_beef_cafe_f00d);
+
+    proc.set_regs(original).unwrap();
+}
+
+#[test]
+fn set_regs_requires_stopped_process() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let regs = proc.get_regs().unwrap();
+    proc.cont().unwrap();
+
+    assert!(proc.set_regs(regs).is_err());
+}
+
+#[test]
+fn registers_match_raw_registers() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let raw = proc.get_regs().unwrap();
+    let regs = proc.registers().unwrap();
+
+    assert_eq!(regs.instruction_pointer(), raw.rip as usize);
+    assert_eq!(regs.stack_pointer(), raw.rsp as usize);
+    assert_eq!(regs.argument(0), Some(raw.rdi as usize));
+}
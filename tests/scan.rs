@@ -0,0 +1,19 @@
+mod common;
+
+use common::{writable_address, Target};
+use memmod::scan::RegionFilter;
+
+#[test]
+fn byte_array_needles_match_unaligned() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc) + 3;
+    let needle = *b"MAGICSTRINGXYZ!!";
+    proc.write_all_at(address, &needle).unwrap();
+
+    let found = proc
+        .scan_value_in(needle, RegionFilter::new().range(address..address + 16))
+        .unwrap();
+
+    assert_eq!(found, [address]);
+}
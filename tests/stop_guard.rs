@@ -0,0 +1,31 @@
+mod common;
+
+use common::Target;
+
+#[test]
+fn guard_stops_and_resumes() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    proc.cont().unwrap();
+    assert_ne!(proc.stat().unwrap().state, 't');
+
+    {
+        let guard = proc.stopped_guard().unwrap();
+        assert!(guard.is_stopped());
+        assert_eq!(guard.stat().unwrap().state, 't');
+    }
+
+    assert!(!proc.is_stopped());
+    assert_ne!(proc.stat().unwrap().state, 't');
+}
+
+#[test]
+fn guard_leaves_stopped_process_stopped() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    drop(proc.stopped_guard().unwrap());
+
+    assert!(proc.is_stopped());
+    assert_eq!(proc.stat().unwrap().state, 't');
+}
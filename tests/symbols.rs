@@ -0,0 +1,16 @@
+mod common;
+
+use common::Target;
+
+#[test]
+fn symbols_resolve_inside_their_module() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let base = proc.module_base("libc.so.6").unwrap().unwrap();
+    let (start, end) = proc.module_range("libc.so.6").unwrap();
+    let address = proc.symbol_address("libc.so.6", "nanosleep").unwrap();
+
+    assert_eq!(base, start);
+    assert!((start..end).contains(&address));
+}
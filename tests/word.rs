@@ -0,0 +1,61 @@
+mod common;
+
+use common::{writable_address, Target};
+
+#[test]
+fn words_round_trip() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+
+    for word in [0, 1, 0x1234_5678, -1, -2, isize::MIN, isize::MAX] {
+        proc.write_word(address, word).unwrap();
+
+        assert_eq!(proc.read_word(address).unwrap(), word);
+        assert_eq!(proc.read_vec(address, 8).unwrap(), word.to_ne_bytes());
+    }
+}
+
+#[test]
+fn negative_words_land_exactly() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.write_all_at(address, &[0; 16]).unwrap();
+
+    proc.write_word(address, -2).unwrap();
+
+    let bytes = proc.read_vec(address, 16).unwrap();
+    assert_eq!(bytes[..8], (-2isize).to_ne_bytes());
+    assert_eq!(bytes[8..], [0; 8]);
+}
+
+#[test]
+fn nostop_writes_land_while_running() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.cont().unwrap();
+
+    proc.write_word_nostop(address, -3).unwrap();
+
+    assert!(!proc.is_stopped());
+    assert_eq!(proc.read_vec(address, 8).unwrap(), (-3isize).to_ne_bytes());
+}
+
+#[test]
+fn writes_without_auto_stop_leave_process_running() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+    let address = writable_address(&mut proc);
+    proc.set_auto_stop(false);
+    proc.cont().unwrap();
+
+    proc.write_word(address, 0x1234).unwrap();
+
+    assert!(!proc.is_stopped());
+    assert_eq!(
+        proc.read_vec(address, 8).unwrap(),
+        0x1234isize.to_ne_bytes()
+    );
+}
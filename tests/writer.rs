@@ -0,0 +1,31 @@
+mod common;
+
+use common::Target;
+
+/// Nothing is ever mapped at the start of the address space.
+const UNMAPPED: usize = 0x10;
+
+#[test]
+fn failed_writes_return_errors() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    assert!(proc.write_all_at(UNMAPPED, &[1, 2, 3]).is_err());
+    assert!(proc.write_cstring(UNMAPPED, "hi").is_err());
+    assert!(proc.write_bool(UNMAPPED, true).is_err());
+    assert!(proc.write_array(UNMAPPED, &[1u32, 2]).is_err());
+    assert!(proc.write_many(&[(UNMAPPED, 1u64)]).is_err());
+}
+
+#[test]
+fn discarded_writer_drops_quietly() {
+    let target = Target::spawn();
+    let mut proc = target.attach();
+
+    let mut writer = proc.writer(UNMAPPED).unwrap();
+    std::io::Write::write_all(&mut writer, &[1, 2, 3]).unwrap();
+    assert!(std::io::Write::flush(&mut writer).is_err());
+
+    writer.discard();
+    assert_eq!(writer.pending(), 0);
+}